@@ -1,38 +1,243 @@
 // crate 루트로부터 모듈 임포트
-use crate::storage::Storage;  // 저장소 모듈
-use crate::todo::Todo;        // Todo 데이터 구조
+use crate::attachments::{self, AttachmentStore}; // 첨부 파일 저장소
+use crate::config::Config;    // 사용자 설정 (필드 스키마)
+use crate::dates;             // 기간 출력 형식
+use crate::editor;            // 외부 편집기로 메모 편집
+use crate::fields::{self, FieldCondition}; // 사용자 정의 필드
+use crate::notify;            // 알림 전송
+use crate::recurrence::Recurrence; // 반복 규칙
+use crate::storage::{Backend, Collection, Query, StorageLock}; // 저장소 백엔드, 검색 조건, 잠금
+use crate::todo::{self, Attachment, CheckItem, Priority, Status, Todo}; // Todo 데이터 구조, 우선순위, 진행 상태
+use chrono::{DateTime, Duration, Local}; // 마감 기한 계산
 use colored::*;               // 터미널 컬러 출력을 위한 크레이트
+use std::cmp::Ordering;       // 목록 정렬 비교 결과
+use std::collections::{BTreeMap, HashMap, HashSet}; // 태그별 집계, 트리 구성
 use std::error::Error;        // 에러 처리를 위한 표준 트레이트
+use std::io::{self, BufRead, IsTerminal, Write}; // 사용자 확인 입력
+use std::path::Path;          // 첨부할 파일 경로
+use std::thread;              // 알림 대기 (sleep)
+use std::time::Duration as StdDuration; // thread::sleep에 사용하는 표준 기간 타입
+
+// "곧 마감"으로 표시할 기간 (일)
+const DUE_SOON_DAYS: i64 = 3;
+
+// 알림 감시 모드에서 다른 todo 명령의 변경 사항을 확인하는 최대 간격 (초)
+const WATCH_POLL_SECONDS: i64 = 30;
+
+// 다른 todo 명령이 저장소를 잠그고 있을 때 기다리는 최대 시간
+pub const LOCK_TIMEOUT: StdDuration = StdDuration::from_secs(5);
+
+// 할일 추가시 함께 지정할 수 있는 선택 항목들
+// Default를 derive하여 필요한 필드만 채워서 사용
+#[derive(Debug, Default)]
+pub struct AddOptions {
+    pub priority: Priority,              // 우선순위
+    pub due: Option<DateTime<Local>>,    // 마감 기한
+    pub tags: Vec<String>,               // 태그
+    pub project: Option<String>,         // 프로젝트 경로
+    pub parent: Option<usize>,           // 상위 할일 ID
+    pub depends_on: Vec<usize>,          // 먼저 끝나야 하는 할일 ID들
+    pub recurrence: Option<Recurrence>,  // 반복 규칙
+    pub fields: Vec<String>,             // 사용자 정의 필드 값 ("이름=값")
+    pub wait: Option<DateTime<Local>>,   // 이 시각까지 목록에서 숨김
+}
+
+// 할일 수정시 변경할 항목들 (None인 항목은 그대로 유지)
+#[derive(Debug, Default)]
+pub struct EditOptions {
+    pub title: Option<String>,           // 새 제목
+    pub priority: Option<Priority>,      // 새 우선순위
+    pub project: Option<String>,         // 새 프로젝트 (빈 문자열이면 제거)
+    pub parent: Option<usize>,           // 새 상위 할일 ID (0이면 최상위로 이동)
+}
+
+// 목록 출력시 적용할 필터 조건
+// 값이 None인 조건은 적용하지 않음
+#[derive(Debug, Default)]
+pub struct ListFilter {
+    pub tag: Option<String>,             // 이 태그가 붙은 할일만
+    pub project: Option<String>,         // 이 프로젝트(하위 프로젝트 포함)의 할일만
+    pub status: Option<Status>,          // 이 상태인 할일만
+    pub conditions: Vec<String>,         // 사용자 정의 필드 조건 (예: "customer=acme")
+    pub due_before: Option<DateTime<Local>>, // 이 시각까지 마감인 할일만
+    pub all: bool,                       // 대기 중(숨김)인 할일도 표시
+}
+
+impl ListFilter {
+    // 저장소 백엔드가 직접 걸러낼 조건 (태그, 상태, 마감 기한)
+    fn query(&self) -> Query {
+        Query {
+            status: self.status,
+            tag: self.tag.clone(),
+            due_before: self.due_before,
+        }
+    }
+
+    // 백엔드가 걸러내지 않는 조건(프로젝트)을 만족하는지 확인
+    fn matches(&self, todo: &Todo) -> bool {
+        self.project
+            .as_deref()
+            .and_then(todo::normalize_project)
+            .is_none_or(|project| todo.in_project(&project))
+    }
+}
+
+// move 명령으로 할일을 옮길 위치
+#[derive(Debug, Clone, Copy)]
+pub enum Placement {
+    Before(usize),                       // 이 ID의 할일 바로 앞
+    Top,                                 // 맨 앞
+    Bottom,                              // 맨 뒤
+}
 
 // TodoApp 구조체 - 애플리케이션의 상태를 관리
 pub struct TodoApp {
     todos: Vec<Todo>,     // Todo 항목들을 저장하는 벡터
-    storage: Storage,     // 파일 시스템과의 상호작용을 담당
+    backend: Box<dyn Backend>, // 할일 목록, 보관함, 휴지통을 읽고 쓰는 저장소 (보관함, 휴지통은 필요할 때만 읽음)
+    attachments: Option<AttachmentStore>, // 첨부 파일 보관 (파일에 저장하지 않는 백엔드는 없음)
+    config: Config,       // 사용자 설정 (필드 스키마 등)
     next_id: usize,       // 다음 할일에 할당할 ID
+    lock: Option<StorageLock>, // 저장소 잠금 - 불러온 뒤 저장이 끝날 때까지 다른 프로세스가 고치지 못하게 함
 }
 
 // TodoApp의 메서드 구현
 impl TodoApp {
-    // 새로운 TodoApp 인스턴스 생성
+    // 주어진 백엔드로 새로운 TodoApp 인스턴스 생성
     // Result 타입을 반환하여 파일 로드 실패 등의 에러 처리
-    pub fn new() -> Result<Self, Box<dyn Error>> {
-        let storage = Storage::new();
-        let todos = storage.load()?;  // ? 연산자로 에러 전파
-        
-        // 기존 할일들 중 가장 큰 ID를 찾아 다음 ID 설정
-        let next_id = todos.iter().map(|t| t.id).max().unwrap_or(0) + 1;
+    // 불러오기 전에 저장소를 잠그고, TodoApp이 drop될 때(명령이 끝날 때) 잠금을 풂
+    pub fn new(backend: Box<dyn Backend>, config: Config) -> Result<Self, Box<dyn Error>> {
+        let lock = backend.lock(LOCK_TIMEOUT)?;
+        let (todos, next_id) = backend.load(Collection::Todos)?;  // ? 연산자로 에러 전파
+        let attachments = backend.dir().map(AttachmentStore::new);
 
-        Ok(Self {
+        let mut app = Self {
             todos,
-            storage,
-            next_id,
-        })
+            backend,
+            attachments,
+            config,
+            next_id: 0,
+            lock: Some(lock),
+        };
+        // 저장된 다음 ID를 그대로 쓰되, 저장된 값이 없는 예전 파일은 지금까지 쓰인 ID 다음부터
+        app.next_id = next_id.unwrap_or(0).max(app.first_unused_id()?);
+
+        // 두 todos.json을 합친 경우처럼 같은 할일은 하나로 합치고, 겹치는 번호는 뒤쪽 할일에 새 번호 부여
+        // 파일에 적힌 순서로 파일 경계를 찾으므로 순서대로 정렬하기 전에 정리
+        let (merged, renumbered) = merge_duplicates(&mut app.todos, app.next_id);
+        normalize_positions(&mut app.todos);
+        if !merged.is_empty() || !renumbered.is_empty() {
+            for uuid in &merged {
+                println!("{}", format!("⚠️  같은 할일이 두 번 있어서 하나로 합쳤습니다: {}", uuid).yellow());
+            }
+            for (uuid, id) in &renumbered {
+                println!("{}", format!("⚠️  번호가 겹쳐서 새 번호를 부여했습니다: {} → #{}", uuid, id).yellow());
+            }
+            app.next_id += renumbered.len();
+            app.save()?;
+        }
+        Ok(app)
+    }
+
+    // 할일 목록과 다음에 발급할 ID를 함께 저장
+    fn save(&self) -> Result<(), io::Error> {
+        self.backend.save(Collection::Todos, &self.todos, Some(self.next_id))
+    }
+
+    // 번호 또는 UUID 앞부분으로 목록의 할일 번호를 찾음
+    pub fn resolve(&self, key: &str) -> Result<usize, String> {
+        resolve_key(&self.todos, key)
+    }
+
+    // 번호 또는 UUID 앞부분으로 보관함의 할일 번호를 찾음
+    pub fn resolve_archived(&self, key: &str) -> Result<usize, String> {
+        let archived = self.backend.load_todos(Collection::Archive).map_err(|e| e.to_string())?;
+        resolve_key(&archived, key)
+    }
+
+    // 번호 또는 UUID 앞부분으로 휴지통의 할일 번호를 찾음
+    pub fn resolve_trashed(&self, key: &str) -> Result<usize, String> {
+        let trashed = self.backend.load_todos(Collection::Trash).map_err(|e| e.to_string())?;
+        resolve_key(&trashed, key)
+    }
+
+    // 목록, 보관함, 휴지통을 통틀어 가장 큰 ID의 다음 값
+    // 보관하거나 지운 할일의 ID를 다시 쓰지 않아야 원래 ID로 복원할 수 있음
+    fn first_unused_id(&self) -> Result<usize, io::Error> {
+        let archived = self.backend.load_todos(Collection::Archive)?;
+        let trashed = self.backend.load_todos(Collection::Trash)?;
+        let max = self.todos.iter().chain(&archived).chain(&trashed).map(|t| t.id).max();
+        Ok(max.unwrap_or(0) + 1)
+    }
+
+    // 잠금을 푼 채로 f를 실행 (편집기, 확인 질문처럼 사용자를 기다리는 동안 다른 명령이 막히지 않도록)
+    // 끝나면 다시 잠그고, 그 사이 다른 명령이 바꾼 내용을 반영하도록 저장소를 새로 읽음
+    fn unlocked<T>(&mut self, f: impl FnOnce() -> T) -> Result<T, Box<dyn Error>> {
+        self.lock = None;
+        let result = f();
+        self.lock = Some(self.backend.lock(LOCK_TIMEOUT)?);
+        self.reload()?;
+        Ok(result)
+    }
+
+    // y/N 확인 - 지금까지 바꾼 내용을 먼저 저장하고 잠금을 푼 채로 답을 기다림
+    // 터미널이 아니면 묻지 않으므로 저장하거나 잠금을 풀지 않음
+    fn ask(&mut self, question: &str) -> Result<bool, Box<dyn Error>> {
+        if !io::stdin().is_terminal() {
+            return Ok(false);
+        }
+        self.save()?;
+        self.unlocked(|| confirm(question))
+    }
+
+    // 저장소에서 할일 목록을 다시 불러옴 (다른 todo 명령이 바꾼 내용 반영)
+    fn reload(&mut self) -> Result<(), Box<dyn Error>> {
+        let (todos, next_id) = self.backend.load(Collection::Todos)?;
+        self.todos = todos;
+        normalize_positions(&mut self.todos);
+        self.next_id = next_id.unwrap_or(0).max(self.first_unused_id()?);
+        Ok(())
     }
 
     // 새로운 할일 추가
-    pub fn add(&mut self, title: String) -> Result<(), Box<dyn Error>> {
+    pub fn add(&mut self, title: String, options: AddOptions) -> Result<(), Box<dyn Error>> {
+        // 상위 할일이 지정된 경우 존재하는지 확인
+        if let Some(parent) = options.parent
+            && !self.todos.iter().any(|t| t.id == parent)
+        {
+            println!("{}", format!("❌ 상위 할 일 ID {} 를 찾을 수 없습니다.", parent).red());
+            return Ok(());
+        }
+        // 의존하는 할일들이 모두 존재하는지 확인
+        // 새 할일은 아직 아무도 의존하지 않으므로 순환이 생길 수 없음
+        if let Some(missing) = options.depends_on.iter().find(|d| !self.todos.iter().any(|t| t.id == **d)) {
+            println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", missing).red());
+            return Ok(());
+        }
+        // 사용자 정의 필드 값을 스키마로 검증
+        let fields = match self.parse_assignments(&options.fields) {
+            Ok(fields) => fields,
+            Err(e) => {
+                println!("{}", format!("❌ {}", e).red());
+                return Ok(());
+            }
+        };
+
         // 새 Todo 인스턴스 생성
-        let todo = Todo::new(self.next_id, title);
+        let mut todo = Todo::new(self.next_id, title);
+        todo.priority = options.priority;
+        todo.due = options.due;
+        for tag in &options.tags {
+            todo.add_tag(tag);
+        }
+        todo.project = options.project.as_deref().and_then(todo::normalize_project);
+        todo.parent = options.parent;
+        todo.depends_on.extend(&options.depends_on);
+        todo.recurrence = options.recurrence;
+        todo.wait = options.wait;
+        todo.position = self.todos.iter().map(|t| t.position).max().unwrap_or(0) + 1;
+        for (name, value) in fields {
+            todo.set_field(&name, value);
+        }
         
         // 성공 메시지 출력 (녹색)
         println!("{}", format!("✨ 새 할 일 추가: {}", todo.title).green());
@@ -42,131 +247,1741 @@ impl TodoApp {
         self.next_id += 1;
         
         // 파일에 저장
-        self.storage.save(&self.todos)?;
+        self.save()?;
         Ok(())
     }
 
-    // 모든 할일 목록 출력
-    pub fn list(&self) {
+    // 필터 조건에 맞는 할일 목록 출력
+    // sort: 정렬 기준 (priority, due, created, id, title 또는 사용자 정의 필드 이름)
+    pub fn list(&self, filter: &ListFilter, sort: Option<&str>) -> Result<(), Box<dyn Error>> {
         // 할일이 없는 경우 처리
         if self.todos.is_empty() {
             println!("{}", "📋 할 일이 없습니다.".yellow());
-            return;
+            return Ok(());
+        }
+
+        // 사용자 정의 필드 조건과 정렬 기준을 스키마로 검증
+        let conditions: Result<Vec<FieldCondition>, String> = filter
+            .conditions
+            .iter()
+            .map(|c| FieldCondition::parse(c, &self.config.fields))
+            .collect();
+        let sort_key = SortKey::parse(sort.unwrap_or("priority"), &self.config);
+        let (conditions, sort_key) = match (conditions, sort_key) {
+            (Ok(conditions), Ok(sort_key)) => (conditions, sort_key),
+            (Err(e), _) | (_, Err(e)) => {
+                println!("{}", format!("❌ {}", e).red());
+                return Ok(());
+            }
+        };
+
+        // 태그, 상태, 마감 기한은 이미 불러온 목록에서 바로 거름 (저장소를 다시 읽지 않음)
+        let now = Local::now();
+        let query = filter.query();
+        let matching: Vec<&Todo> = self
+            .todos
+            .iter()
+            .filter(|t| query.matches(t))
+            .filter(|t| filter.matches(t))
+            .filter(|t| conditions.iter().all(|c| c.matches(t.fields.get(&c.field))))
+            .collect();
+
+        // 시작일이 아직 오지 않은 할일은 --all 이 아니면 숨김
+        let (mut sorted, hidden): (Vec<&Todo>, Vec<&Todo>) =
+            matching.into_iter().partition(|t| filter.all || !t.is_waiting(now));
+        if sorted.is_empty() {
+            println!("{}", "📋 조건에 맞는 할 일이 없습니다.".yellow());
+            if !hidden.is_empty() {
+                println!("{}", format!("💤 대기 중인 할 일 {}개가 숨겨져 있습니다. (--all 로 보기)", hidden.len()).dimmed());
+            }
+            return Ok(());
         }
 
         // 헤더 출력
         println!("{}", "\n📋 할 일 목록:".bold());
         println!("{}", "─".repeat(50));
         
-        // 각 할일을 상태에 따라 다른 스타일로 출력
-        for todo in &self.todos {
-            let display = if todo.completed {
-                // 완료된 항목: 취소선 + 흐림 효과
-                format!("{}", todo).strikethrough().dimmed()
-            } else {
-                // 미완료 항목: 일반 스타일
-                format!("{}", todo).normal()
-            };
-            println!("{}", display);
+        // 정렬 기준에 따라 정렬, 같으면 우선순위 순, 그래도 같으면 move로 정한 순서
+        sorted.sort_by(|a, b| {
+            sort_key
+                .compare(a, b)
+                .then(a.priority.cmp(&b.priority))
+                .then(a.position.cmp(&b.position))
+        });
+
+        // 트리 구성: 상위 할일이 목록에 보이면 그 아래에, 아니면 최상위에 표시
+        let visible: HashSet<usize> = sorted.iter().map(|t| t.id).collect();
+        let mut children: HashMap<usize, Vec<&Todo>> = HashMap::new();
+        let mut roots: Vec<&Todo> = Vec::new();
+        for todo in &sorted {
+            match todo.parent.filter(|p| visible.contains(p)) {
+                Some(parent) => children.entry(parent).or_default().push(todo),
+                None => roots.push(todo),
+            }
+        }
+
+        // 각 할일을 상태와 우선순위에 따라 다른 스타일로 출력
+        for todo in roots {
+            self.print_tree(todo, 0, &children, now);
         }
         
-        // 요약 정보 출력
-        let total = self.todos.len();
-        let completed = self.todos.iter().filter(|t| t.completed).count();
+        // 요약 정보 출력 (필터가 적용된 항목 기준)
+        let total = sorted.len();
+        let completed = sorted.iter().filter(|t| t.is_closed()).count();
         println!("{}", "─".repeat(50));
         println!(
             "{}",
             format!("전체: {} | 완료: {} | 미완료: {}", total, completed, total - completed).cyan()
         );
+
+        // 기한이 지났거나 오늘 마감인 할일이 있으면 따로 알림
+        let overdue = sorted.iter().filter(|t| t.is_overdue(now)).count();
+        let due_today = sorted.iter().filter(|t| t.is_due_today(now)).count();
+        if overdue > 0 {
+            println!("{}", format!("⚠️  기한이 지난 할 일: {}", overdue).red().bold());
+        }
+        if due_today > 0 {
+            println!("{}", format!("⏰ 오늘 마감인 할 일: {}", due_today).yellow());
+        }
+        if !hidden.is_empty() {
+            println!("{}", format!("💤 대기 중인 할 일 {}개가 숨겨져 있습니다. (--all 로 보기)", hidden.len()).dimmed());
+        }
+        Ok(())
     }
 
-    // 특정 ID의 할일 완료 상태 토글
-    pub fn toggle(&mut self, id: usize) -> Result<(), Box<dyn Error>> {
-        // iter_mut()로 가변 반복자를 얻어 직접 수정
+    // 할일 하나를 출력하고 그 하위 할일들을 들여쓰기하여 재귀적으로 출력
+    fn print_tree(
+        &self,
+        todo: &Todo,
+        depth: usize,
+        children: &HashMap<usize, Vec<&Todo>>,
+        now: DateTime<Local>,
+    ) {
+        let blockers = self.open_dependencies(todo);
+        let display = match todo.status {
+            // 완료/취소된 항목: 취소선 + 흐림 효과
+            Status::Done | Status::Cancelled => format!("{}", todo).strikethrough().dimmed(),
+            // 막힌 항목이나 다른 할일을 기다리는 항목: 흐림 효과
+            Status::Blocked => format!("{}", todo).magenta().dimmed(),
+            _ if !blockers.is_empty() => format!("{}", todo).dimmed(),
+            // 진행 중인 항목: 굵게 + 밑줄
+            Status::InProgress => format!("{}", todo).green().bold().underline(),
+            // 할 일: 우선순위별 색상
+            Status::Todo => match todo.priority {
+                Priority::High => format!("{}", todo).red().bold(),
+                Priority::Medium => format!("{}", todo).yellow(),
+                Priority::Low => format!("{}", todo).blue(),
+                Priority::None => format!("{}", todo).normal(),
+            },
+        };
+
+        // 하위 할일이 있으면 n/m 완료 표시
+        let (done, total) = self.children_progress(todo.id);
+        let progress = if total > 0 {
+            format!(" {}", format!("[{}/{}]", done, total).cyan())
+        } else {
+            String::new()
+        };
+        // 체크리스트가 있으면 체크된 수/전체 수 표시
+        let (checked, items) = todo.checklist_progress();
+        let checklist = if items > 0 {
+            format!(" {}", format!("☑ {}/{}", checked, items).cyan())
+        } else {
+            String::new()
+        };
+
+        let indent = if depth > 0 {
+            format!("{}└ ", "   ".repeat(depth - 1))
+        } else {
+            String::new()
+        };
+        // 아직 끝나지 않은 선행 할일 표시
+        let blocked = if blockers.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", format!("⛔ 대기: {}", id_list(blockers)).dimmed())
+        };
+        println!("{}{}{}{}{}{}", indent, display, progress, checklist, due_badge(todo, now), blocked);
+
+        // 체크리스트 항목은 할일 바로 아래에 하위 할일보다 먼저 표시
+        let item_indent = "   ".repeat(depth + 1);
+        for (i, item) in todo.checklist.iter().enumerate() {
+            println!("{}{}", item_indent, check_line(i + 1, item));
+        }
+
+        for child in children.get(&todo.id).into_iter().flatten() {
+            self.print_tree(child, depth + 1, children, now);
+        }
+    }
+
+    // 아직 완료되지 않은 선행 할일 ID 목록 (비어 있으면 바로 진행 가능)
+    // 삭제되어 존재하지 않는 ID는 무시
+    fn open_dependencies(&self, todo: &Todo) -> Vec<usize> {
+        todo.depends_on
+            .iter()
+            .copied()
+            .filter(|dep| self.todos.iter().any(|t| t.id == *dep && !t.is_closed()))
+            .collect()
+    }
+
+    // 할일 하나의 모든 정보를 자세히 출력 (메모 포함)
+    pub fn show(&self, id: usize) {
+        let Some(todo) = self.todos.iter().find(|t| t.id == id) else {
+            println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            return;
+        };
+
+        let format_time = |time: DateTime<Local>| time.format("%Y-%m-%d %H:%M").to_string();
+
+        println!("{}", format!("\n[{}] {}", todo.id, todo.title).bold());
+        println!("{}", "─".repeat(50));
+        println!("UUID:       {}", todo.uuid);
+        println!("상태:       {} {}", todo.status.symbol(), todo.status.label());
+        println!("우선순위:   {}", todo.priority.label());
+        if let Some(due) = todo.due {
+            println!("마감:       {}{}", format_time(due), due_badge(todo, Local::now()));
+        }
+        if let Some(wait) = todo.wait {
+            let waiting = if todo.is_waiting(Local::now()) { " (대기 중)" } else { "" };
+            println!("시작:       {}{}", format_time(wait), waiting);
+        }
+        if let Some(recurrence) = &todo.recurrence {
+            println!("반복:       {}", recurrence);
+        }
+        for reminder in &todo.reminders {
+            let fired = if reminder.fired { " (알림 완료)" } else { "" };
+            println!("알림:       {}{}", format_time(reminder.at), fired.dimmed());
+        }
+        if !todo.tags.is_empty() {
+            let tags: Vec<String> = todo.tags.iter().map(|t| format!("#{}", t)).collect();
+            println!("태그:       {}", tags.join(" "));
+        }
+        if let Some(project) = &todo.project {
+            println!("프로젝트:   @{}", project);
+        }
+        if let Some(parent) = todo.parent {
+            println!("상위 할 일: #{}", parent);
+        }
+        let (done, total) = self.children_progress(todo.id);
+        if total > 0 {
+            let children = self.todos.iter().filter(|t| t.parent == Some(todo.id)).map(|t| t.id);
+            println!("하위 할 일: {} ({}/{} 완료)", id_list(children), done, total);
+        }
+        if !todo.depends_on.is_empty() {
+            println!("선행 할 일: {}", id_list(todo.depends_on.iter().copied()));
+        }
+        for (name, value) in &todo.fields {
+            // 설정 파일에서 지워진 필드도 값은 남아 있으므로 표시만 구분
+            let undefined = if fields::find(&self.config.fields, name).is_err() { " (정의되지 않은 필드)" } else { "" };
+            println!("{:<11} {}{}", format!("{}:", name), value, undefined.dimmed());
+        }
+        if !todo.time_entries.is_empty() {
+            let tracked = dates::format_duration(todo.tracked_time(Local::now()));
+            let running = if todo.is_tracking() { " (진행 중)" } else { "" };
+            println!("기록 시간:  {}{}", tracked, running);
+        }
+        println!("생성:       {}", format_time(todo.created_at));
+        println!("수정:       {}", format_time(todo.updated_at));
+
+        // 시간 기록 구간
+        if !todo.time_entries.is_empty() {
+            println!("{}", "─".repeat(50));
+            println!("{}", "⏱️  시간 기록:".bold());
+            for entry in &todo.time_entries {
+                let end = entry.end.map_or("진행 중".to_string(), format_time);
+                let duration = dates::format_duration(entry.duration(Local::now()));
+                println!("  {} ~ {} ({})", format_time(entry.start), end, duration);
+            }
+        }
+
+        // 체크리스트
+        if !todo.checklist.is_empty() {
+            let (checked, items) = todo.checklist_progress();
+            println!("{}", "─".repeat(50));
+            println!("{}", format!("☑ 체크리스트 ({}/{}):", checked, items).bold());
+            for (i, item) in todo.checklist.iter().enumerate() {
+                println!("  {}", check_line(i + 1, item));
+            }
+        }
+
+        // 첨부 (번호는 todo open에 사용)
+        if !todo.attachments.is_empty() {
+            println!("{}", "─".repeat(50));
+            println!("{}", "📎 첨부:".bold());
+            for (i, attachment) in todo.attachments.iter().enumerate() {
+                println!("  {}. {}", i + 1, attachment);
+            }
+        }
+
+        // 메모는 여러 줄이므로 구분선 아래에 그대로 출력
+        if let Some(notes) = &todo.notes {
+            println!("{}", "─".repeat(50));
+            println!("{}", "📝 메모:".bold());
+            println!("{}", notes);
+        }
+    }
+
+    // 메모 편집 - message가 주어지면 그대로 저장하고, 없으면 $EDITOR로 편집
+    pub fn note(&mut self, id: usize, message: Option<String>) -> Result<(), Box<dyn Error>> {
+        let Some(todo) = self.todos.iter().find(|t| t.id == id) else {
+            println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            return Ok(());
+        };
+
+        // 편집기를 띄워 둔 동안에는 잠금을 풀어 다른 명령이 기다리지 않게 함
+        let notes = match message {
+            Some(message) => message,
+            None => {
+                let current = todo.notes.clone().unwrap_or_default();
+                self.unlocked(|| editor::edit_text(&format!("note-{}", id), &current))??
+            }
+        };
+
+        // 편집하는 동안 다른 명령이 지웠을 수 있으므로 다시 불러온 목록에서 찾음
+        let Some(todo) = self.todos.iter_mut().find(|t| t.id == id) else {
+            println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            return Ok(());
+        };
+
+        // 내용이 바뀌지 않았으면 저장하지 않음
+        if notes.trim_end() == todo.notes.as_deref().unwrap_or("") {
+            println!("{}", "📝 메모가 변경되지 않았습니다.".yellow());
+            return Ok(());
+        }
+
+        todo.set_notes(&notes);
+        let msg = if todo.notes.is_some() {
+            format!("📝 메모 저장: {}", todo.title)
+        } else {
+            format!("📝 메모 삭제: {}", todo.title)
+        };
+        println!("{}", msg.green());
+        self.save()?;
+        Ok(())
+    }
+
+    // 사용자 정의 필드 값 설정 ("이름=값", 값이 비어 있으면 제거)
+    pub fn set_fields(&mut self, id: usize, assignments: &[String]) -> Result<(), Box<dyn Error>> {
+        // 하나라도 잘못된 값이 있으면 아무것도 바꾸지 않음
+        let fields = match self.parse_assignments(assignments) {
+            Ok(fields) => fields,
+            Err(e) => {
+                println!("{}", format!("❌ {}", e).red());
+                return Ok(());
+            }
+        };
+
         match self.todos.iter_mut().find(|t| t.id == id) {
             Some(todo) => {
-                todo.toggle();
-                
-                // 상태에 따른 메시지 출력
-                let msg = if todo.completed {
-                    format!("✅ 완료: {}", todo.title).green()
+                for (name, value) in fields {
+                    let msg = match &value {
+                        Some(value) => format!("🧩 {} = {}", name, value).green(),
+                        None => format!("🧩 {} 제거", name).yellow(),
+                    };
+                    todo.set_field(&name, value);
+                    println!("{}", msg);
+                }
+                self.save()?;
+            }
+            None => {
+                println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            }
+        }
+        Ok(())
+    }
+
+    // "이름=값" 목록을 스키마로 검증하여 (이름, 값) 목록으로 변환
+    // 값이 비어 있으면 제거를 뜻하는 None
+    fn parse_assignments(&self, assignments: &[String]) -> Result<Vec<(String, Option<fields::FieldValue>)>, String> {
+        assignments
+            .iter()
+            .map(|assignment| {
+                let (name, value) = assignment
+                    .split_once('=')
+                    .ok_or_else(|| format!("'이름=값' 형식이어야 합니다: '{}'", assignment))?;
+                let def = fields::find(&self.config.fields, name.trim())?;
+                let value = if value.trim().is_empty() {
+                    None
                 } else {
-                    format!("⏸️  미완료로 변경: {}", todo.title).yellow()
+                    Some(def.parse_value(value)?)
                 };
-                println!("{}", msg);
-                
-                // 변경사항 저장
-                self.storage.save(&self.todos)?;
-                Ok(())
+                Ok((def.name.clone(), value))
+            })
+            .collect()
+    }
+
+    // 설정 파일에 정의된 사용자 정의 필드 목록 출력
+    pub fn fields(&self) {
+        if self.config.fields.is_empty() {
+            println!("{}", "🧩 정의된 필드가 없습니다. todo_config.json 에 fields를 추가하세요.".yellow());
+            return;
+        }
+
+        println!("{}", "\n🧩 사용자 정의 필드:".bold());
+        println!("{}", "─".repeat(30));
+        for def in &self.config.fields {
+            let used = self.todos.iter().filter(|t| t.fields.contains_key(&def.name)).count();
+            println!("{:<15} {} (사용: {})", def.name, def.describe(), used);
+        }
+    }
+
+    // 시작일을 미뤄서 그때까지 목록에서 숨김 (None이면 바로 다시 표시)
+    pub fn snooze(&mut self, id: usize, until: Option<DateTime<Local>>) -> Result<(), Box<dyn Error>> {
+        match self.todos.iter_mut().find(|t| t.id == id) {
+            Some(todo) => {
+                todo.set_wait(until);
+                let msg = match until {
+                    Some(until) => format!("💤 {} 까지 숨김: {}", until.format("%Y-%m-%d %H:%M"), todo.title),
+                    None => format!("👀 다시 표시: {}", todo.title),
+                };
+                println!("{}", msg.green());
+                self.save()?;
             }
             None => {
-                // ID를 찾을 수 없는 경우
                 println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
-                Ok(())
             }
         }
+        Ok(())
     }
 
-    // 특정 ID의 할일 삭제
-    pub fn delete(&mut self, id: usize) -> Result<(), Box<dyn Error>> {
-        // position()으로 인덱스를 찾고 remove()로 삭제
-        if let Some(pos) = self.todos.iter().position(|t| t.id == id) {
-            let removed = self.todos.remove(pos);
-            println!("{}", format!("🗑️  삭제됨: {}", removed.title).red());
-            self.storage.save(&self.todos)?;
+    // 알림 추가 (clear가 true이면 해당 할일의 알림을 모두 제거)
+    pub fn remind(&mut self, id: usize, at: Option<DateTime<Local>>, clear: bool) -> Result<(), Box<dyn Error>> {
+        let Some(todo) = self.todos.iter_mut().find(|t| t.id == id) else {
+            println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            return Ok(());
+        };
+
+        if clear {
+            todo.reminders.clear();
+            todo.updated_at = Local::now();
+            println!("{}", format!("🔕 알림 모두 제거: {}", todo.title).yellow());
+        } else if let Some(at) = at {
+            if todo.add_reminder(at) {
+                println!("{}", format!("🔔 알림 추가: {} ({})", todo.title, at.format("%Y-%m-%d %H:%M")).green());
+                if at <= Local::now() {
+                    println!("{}", "⚠️  이미 지난 시각입니다. todo remind --watch 를 실행하면 바로 알립니다.".yellow());
+                }
+            } else {
+                println!("{}", "이미 같은 시각의 알림이 있습니다.".yellow());
+                return Ok(());
+            }
+        } else {
+            println!("{}", "❌ 알림 시각을 지정하세요. (예: todo remind 3 30m)".red());
+            return Ok(());
+        }
+        self.save()?;
+        Ok(())
+    }
+
+    // 아직 울리지 않은 알림 목록을 시간 순으로 출력
+    pub fn reminders(&self) {
+        let mut pending: Vec<(DateTime<Local>, &Todo)> = self
+            .todos
+            .iter()
+            .filter(|t| !t.is_closed())
+            .flat_map(|t| t.reminders.iter().filter(|r| !r.fired).map(move |r| (r.at, t)))
+            .collect();
+
+        if pending.is_empty() {
+            println!("{}", "🔕 예정된 알림이 없습니다.".yellow());
+            return;
+        }
+
+        pending.sort_by_key(|(at, _)| *at);
+        println!("{}", "\n🔔 예정된 알림:".bold());
+        println!("{}", "─".repeat(50));
+        for (at, todo) in pending {
+            println!("{}  [{}] {}", at.format("%Y-%m-%d %H:%M"), todo.id, todo.title);
+        }
+    }
+
+    // 알림 감시 모드 - 종료(Ctrl+C)할 때까지 실행
+    // 매번 저장소를 다시 읽어서 다른 todo 명령으로 추가/변경된 알림도 반영
+    // 기다리는 동안에는 다른 명령이 저장할 수 있도록 잠금을 풀어 둠
+    pub fn watch_reminders(&mut self) -> Result<(), Box<dyn Error>> {
+        println!("{}", "👀 알림 감시를 시작합니다. (종료: Ctrl+C)".cyan());
+        loop {
+            // 다른 명령이 오래 잡고 있어도 감시를 멈추지 않고 다시 시도
+            if self.lock.is_none() {
+                match self.backend.lock(LOCK_TIMEOUT) {
+                    Ok(lock) => self.lock = Some(lock),
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                        println!("{}", "⏳ 다른 명령이 저장소를 사용 중입니다. 잠시 후 다시 확인합니다.".dimmed());
+                        continue;
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            self.reload()?;
+            let now = Local::now();
+
+            // 시각이 된 알림을 다시 울리지 않도록 표시하고 저장
+            let mut to_send = Vec::new();
+            for todo in self.todos.iter_mut().filter(|t| !t.is_closed()) {
+                let due: Vec<usize> = (0..todo.reminders.len())
+                    .filter(|&i| !todo.reminders[i].fired && todo.reminders[i].at <= now)
+                    .collect();
+                if due.is_empty() {
+                    continue;
+                }
+                for i in due {
+                    todo.reminders[i].fired = true;
+                }
+                to_send.push(todo.clone());
+            }
+            if !to_send.is_empty() {
+                self.save()?;
+            }
+
+            // 알림 명령이 사용자의 응답을 기다려도 다른 명령이 막히지 않도록 잠금을 푼 뒤 보냄
+            self.lock = None;
+            for todo in &to_send {
+                if let Err(e) = notify::send(todo, self.config.remind_command.as_deref()) {
+                    println!("{}", format!("❌ 알림 실패: {}", e).red());
+                }
+            }
+
+            // 다음 알림 시각까지 대기하되, 다른 명령의 변경을 반영하도록 최대 간격을 둠
+            let poll = now + Duration::seconds(WATCH_POLL_SECONDS);
+            let wake = self
+                .todos
+                .iter()
+                .filter(|t| !t.is_closed())
+                .flat_map(|t| t.reminders.iter().filter(|r| !r.fired).map(|r| r.at))
+                .min()
+                .map_or(poll, |at| at.min(poll));
+            let sleep = (wake - Local::now()).to_std().unwrap_or_default();
+            thread::sleep(sleep.max(StdDuration::from_millis(200)));
+        }
+    }
+
+    // 체크리스트 항목 추가
+    pub fn check_add(&mut self, id: usize, text: &str) -> Result<(), Box<dyn Error>> {
+        if text.trim().is_empty() {
+            println!("{}", "❌ 체크리스트 항목 내용이 비어 있습니다.".red());
+            return Ok(());
+        }
+        match self.todos.iter_mut().find(|t| t.id == id) {
+            Some(todo) => {
+                let n = todo.add_check_item(text);
+                println!("{}", format!("☐ 체크리스트 {}번 추가: {} ({})", n, text.trim(), todo.title).green());
+                self.save()?;
+            }
+            None => {
+                println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            }
+        }
+        Ok(())
+    }
+
+    // n번째 체크리스트 항목 체크/해제
+    pub fn check_toggle(&mut self, id: usize, n: usize) -> Result<(), Box<dyn Error>> {
+        let Some(todo) = self.todos.iter_mut().find(|t| t.id == id) else {
+            println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            return Ok(());
+        };
+        let Some(done) = todo.toggle_check_item(n) else {
+            println!("{}", format!("❌ 체크리스트 {}번 항목이 없습니다. ({}개 있음)", n, todo.checklist.len()).red());
+            return Ok(());
+        };
+
+        let item = &todo.checklist[n - 1];
+        if done {
+            println!("{}", format!("☑ 체크: {}", item.text).green());
         } else {
+            println!("{}", format!("☐ 체크 해제: {}", item.text).yellow());
+        }
+        // 모든 항목을 체크하면 할일 완료를 안내 (자동으로 완료하지는 않음)
+        let (checked, items) = todo.checklist_progress();
+        if done && checked == items && !todo.is_closed() {
+            println!("{}", format!("🎉 체크리스트를 모두 마쳤습니다! (완료하려면: todo toggle {})", id).cyan());
+        }
+        self.save()?;
+        Ok(())
+    }
+
+    // n번째 체크리스트 항목 제거 (뒤 항목들의 번호는 하나씩 당겨짐)
+    pub fn check_remove(&mut self, id: usize, n: usize) -> Result<(), Box<dyn Error>> {
+        let Some(todo) = self.todos.iter_mut().find(|t| t.id == id) else {
             println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            return Ok(());
+        };
+        match todo.remove_check_item(n) {
+            Some(item) => {
+                println!("{}", format!("🗑️  체크리스트 항목 삭제: {}", item.text).red());
+                self.save()?;
+            }
+            None => {
+                println!("{}", format!("❌ 체크리스트 {}번 항목이 없습니다. ({}개 있음)", n, todo.checklist.len()).red());
+            }
         }
         Ok(())
     }
 
-    // 완료된 모든 할일 삭제
-    pub fn clear_completed(&mut self) -> Result<(), Box<dyn Error>> {
-        let before_count = self.todos.len();
-        
-        // retain()으로 미완료 항목만 유지
-        self.todos.retain(|todo| !todo.completed);
-        
-        let removed_count = before_count - self.todos.len();
-        
-        if removed_count > 0 {
-            println!("{}", format!("🗑️  {} 개의 완료된 할 일이 삭제되었습니다.", removed_count).red());
-            self.storage.save(&self.todos)?;
+    // 파일이나 링크 첨부
+    // 존재하는 파일이면 첨부 저장소로 복사하고, URL이면 링크로 저장
+    pub fn attach(&mut self, id: usize, target: &str) -> Result<(), Box<dyn Error>> {
+        if !self.todos.iter().any(|t| t.id == id) {
+            println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            return Ok(());
+        }
+
+        let path = Path::new(target);
+        let attachment = if path.is_file() {
+            let Some(store) = &self.attachments else {
+                println!("{}", "❌ 이 저장소에는 파일을 첨부할 수 없습니다. (링크만 가능)".red());
+                return Ok(());
+            };
+            let name = path.file_name().map_or(target.to_string(), |n| n.to_string_lossy().into_owned());
+            let file = store.store(path)?;
+            Attachment::File { name, file }
+        } else if attachments::is_url(target) {
+            Attachment::Link { url: target.to_string() }
         } else {
-            println!("{}", "완료된 할 일이 없습니다.".yellow());
+            println!("{}", format!("❌ 파일을 찾을 수 없습니다: {}", target).red());
+            return Ok(());
+        };
+
+        if let Some(todo) = self.todos.iter_mut().find(|t| t.id == id) {
+            let msg = format!("📎 첨부 추가: {} ({})", attachment, todo.title);
+            if todo.add_attachment(attachment) {
+                println!("{}", msg.green());
+                self.save()?;
+            } else {
+                println!("{}", "이미 첨부되어 있습니다.".yellow());
+            }
         }
         Ok(())
     }
 
-    // 통계 정보 출력
-    pub fn stats(&self) {
-        let total = self.todos.len();
-        let completed = self.todos.iter().filter(|t| t.completed).count();
-        let pending = total - completed;
+    // n번째 첨부 제거 (더 이상 쓰이지 않는 파일은 저장소에서도 삭제)
+    pub fn detach(&mut self, id: usize, n: usize) -> Result<(), Box<dyn Error>> {
+        let Some(todo) = self.todos.iter_mut().find(|t| t.id == id) else {
+            println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            return Ok(());
+        };
+        match todo.remove_attachment(n) {
+            Some(attachment) => {
+                println!("{}", format!("🗑️  첨부 제거: {}", attachment).red());
+                self.save()?;
+                self.collect_attachment_garbage()?;
+            }
+            None => {
+                println!("{}", format!("❌ {}번 첨부가 없습니다. ({}개 있음)", n, todo.attachments.len()).red());
+            }
+        }
+        Ok(())
+    }
 
-        // 통계 헤더
-        println!("{}", "\n📊 통계:".bold());
-        println!("{}", "─".repeat(30));
-        
-        // 기본 통계
-        println!("전체 할 일: {}", total);
-        println!("완료됨: {} {}", completed, "✅".repeat(completed.min(10)));
-        println!("미완료: {} {}", pending, "⏳".repeat(pending.min(10)));
-        
-        // 진행률 표시
-        if total > 0 {
-            let percentage = (completed as f64 / total as f64 * 100.0) as u32;
-            println!("완료율: {}%", percentage);
-            
-            // 프로그레스 바 생성
-            let bar_length = 20usize;
-            let filled = (bar_length * percentage as usize / 100);
-            let bar = format!(
-                "[{}{}]",
-                "█".repeat(filled).green(),
-                "░".repeat(bar_length - filled).dimmed()
-            );
-            println!("{}", bar);
+    // 첨부 열기 - 번호가 없으면 목록을 보여주고, 첨부가 하나뿐이면 바로 열기
+    pub fn open(&self, id: usize, n: Option<usize>) -> Result<(), Box<dyn Error>> {
+        let Some(todo) = self.todos.iter().find(|t| t.id == id) else {
+            println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            return Ok(());
+        };
+        if todo.attachments.is_empty() {
+            println!("{}", format!("📎 첨부가 없습니다: {}", todo.title).yellow());
+            return Ok(());
+        }
+
+        let n = match n {
+            Some(n) => n,
+            None if todo.attachments.len() == 1 => 1,
+            None => {
+                println!("{}", format!("\n📎 [{}] {} 의 첨부:", todo.id, todo.title).bold());
+                for (i, attachment) in todo.attachments.iter().enumerate() {
+                    println!("  {}. {}", i + 1, attachment);
+                }
+                println!("{}", format!("열려면: todo open {} <번호>", id).dimmed());
+                return Ok(());
+            }
+        };
+        let Some(attachment) = n.checked_sub(1).and_then(|i| todo.attachments.get(i)) else {
+            println!("{}", format!("❌ {}번 첨부가 없습니다. ({}개 있음)", n, todo.attachments.len()).red());
+            return Ok(());
+        };
+
+        let target = match attachment {
+            Attachment::Link { url } => url.clone(),
+            Attachment::File { file, .. } => {
+                let Some(path) = self.attachments.as_ref().map(|store| store.path(file)) else {
+                    println!("{}", "❌ 이 저장소에서는 첨부 파일을 열 수 없습니다.".red());
+                    return Ok(());
+                };
+                if !path.exists() {
+                    println!("{}", format!("❌ 첨부 파일이 저장소에 없습니다: {}", path.display()).red());
+                    return Ok(());
+                }
+                path.to_string_lossy().into_owned()
+            }
+        };
+        println!("{}", format!("📂 열기: {}", attachment).cyan());
+        attachments::open(&target)?;
+        Ok(())
+    }
+
+    // 어떤 할일도 참조하지 않는 첨부 파일을 저장소에서 삭제
+    // 보관함과 휴지통의 할일이 참조하는 파일도 남겨 둠 (복원할 수 있도록)
+    fn collect_attachment_garbage(&self) -> Result<(), Box<dyn Error>> {
+        let Some(store) = &self.attachments else {
+            return Ok(());
+        };
+        let archived = self.backend.load_todos(Collection::Archive)?;
+        let trashed = self.backend.load_todos(Collection::Trash)?;
+        let referenced: HashSet<&str> = self
+            .todos
+            .iter()
+            .chain(&archived)
+            .chain(&trashed)
+            .flat_map(|t| &t.attachments)
+            .filter_map(|a| match a {
+                Attachment::File { file, .. } => Some(file.as_str()),
+                Attachment::Link { .. } => None,
+            })
+            .collect();
+
+        let removed = store.collect_garbage(&referenced)?;
+        if removed > 0 {
+            println!("{}", format!("📎 사용하지 않는 첨부 파일 {}개를 정리했습니다.", removed).dimmed());
         }
+        Ok(())
     }
-}
\ No newline at end of file
+
+    // 타이머 시작 - 동시에 하나의 타이머만 돌 수 있음
+    pub fn start(&mut self, id: usize) -> Result<(), Box<dyn Error>> {
+        if let Some(running) = self.todos.iter().find(|t| t.is_tracking()) {
+            let msg = if running.id == id {
+                format!("⏱️  이미 타이머가 실행 중입니다: [{}] {}", running.id, running.title)
+            } else {
+                format!(
+                    "❌ [{}] {} 의 타이머가 실행 중입니다. 먼저 todo stop 으로 멈추세요.",
+                    running.id, running.title
+                )
+            };
+            println!("{}", msg.yellow());
+            return Ok(());
+        }
+
+        match self.todos.iter_mut().find(|t| t.id == id) {
+            Some(todo) if todo.is_closed() => {
+                println!("{}", format!("❌ 이미 완료되었거나 취소된 할 일입니다: {}", todo.title).red());
+            }
+            Some(todo) => {
+                todo.start_timer(Local::now());
+                println!("{}", format!("⏱️  타이머 시작: {}", todo.title).green());
+                self.save()?;
+            }
+            None => {
+                println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            }
+        }
+        Ok(())
+    }
+
+    // 실행 중인 타이머 정지
+    pub fn stop(&mut self) -> Result<(), Box<dyn Error>> {
+        let now = Local::now();
+        match self.todos.iter_mut().find(|t| t.is_tracking()) {
+            Some(todo) => {
+                let elapsed = todo.stop_timer(now).unwrap_or_default();
+                println!(
+                    "{}",
+                    format!(
+                        "⏹️  타이머 정지: {} (이번: {}, 누적: {})",
+                        todo.title,
+                        dates::format_duration(elapsed),
+                        dates::format_duration(todo.tracked_time(now))
+                    )
+                    .green()
+                );
+                self.save()?;
+            }
+            None => {
+                println!("{}", "실행 중인 타이머가 없습니다.".yellow());
+            }
+        }
+        Ok(())
+    }
+
+    // 지금 바로 진행할 수 있는 할일만 출력 (막히지 않은 미완료 할일이면서 선행 할일이 모두 끝난 것)
+    // 시작일이 아직 오지 않은 할일도 제외
+    pub fn next(&self) {
+        let now = Local::now();
+        let mut actionable: Vec<&Todo> = self
+            .todos
+            .iter()
+            .filter(|t| {
+                !t.is_closed()
+                    && t.status != Status::Blocked
+                    && !t.is_waiting(now)
+                    && self.open_dependencies(t).is_empty()
+            })
+            .collect();
+
+        if actionable.is_empty() {
+            println!("{}", "🎉 지금 진행할 수 있는 할 일이 없습니다.".yellow());
+            return;
+        }
+
+        // 우선순위가 높은 것부터, 같은 우선순위라면 마감이 빠른 것부터 (기한 없는 것은 뒤로)
+        // 이미 진행 중인 할일을 가장 먼저 보여줌
+        actionable.sort_by_key(|t| (t.status != Status::InProgress, t.priority, t.due.is_none(), t.due));
+
+        println!("{}", "\n👉 다음에 할 일:".bold());
+        println!("{}", "─".repeat(50));
+        for todo in actionable {
+            println!("{}{}", todo, due_badge(todo, now));
+        }
+    }
+
+    // 의존 관계 추가 (remove가 true이면 제거)
+    // 순환 의존이 생기는 경우 해당 의존은 추가하지 않음
+    pub fn depend(&mut self, id: usize, deps: &[usize], remove: bool) -> Result<(), Box<dyn Error>> {
+        if !self.todos.iter().any(|t| t.id == id) {
+            println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            return Ok(());
+        }
+
+        for &dep in deps {
+            if remove {
+                if let Some(todo) = self.todos.iter_mut().find(|t| t.id == id)
+                    && todo.depends_on.remove(&dep)
+                {
+                    println!("{}", format!("🔓 #{} 이(가) 더 이상 #{} 를 기다리지 않습니다.", id, dep).yellow());
+                }
+                continue;
+            }
+
+            if !self.todos.iter().any(|t| t.id == dep) {
+                println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", dep).red());
+            } else if creates_cycle(&self.todos, id, dep) {
+                println!("{}", format!("❌ #{} → #{} 의존을 추가하면 순환이 생깁니다.", id, dep).red());
+            } else if let Some(todo) = self.todos.iter_mut().find(|t| t.id == id)
+                && todo.depends_on.insert(dep)
+            {
+                println!("{}", format!("🔗 #{} 은(는) 이제 #{} 가 끝나야 진행할 수 있습니다.", id, dep).green());
+            }
+        }
+
+        if let Some(todo) = self.todos.iter_mut().find(|t| t.id == id) {
+            todo.updated_at = Local::now();
+        }
+        self.save()?;
+        Ok(())
+    }
+
+    // 직접 하위 할일 중 (완료된 수, 전체 수)
+    fn children_progress(&self, id: usize) -> (usize, usize) {
+        let children = self.todos.iter().filter(|t| t.parent == Some(id));
+        children.fold((0, 0), |(done, total), t| {
+            (done + usize::from(t.is_closed()), total + 1)
+        })
+    }
+
+    // 주어진 할일의 모든 하위 할일 ID (자식, 손자, ...)
+    fn descendants(&self, id: usize) -> Vec<usize> {
+        let mut result = Vec::new();
+        let mut stack = vec![id];
+        while let Some(current) = stack.pop() {
+            for child in self.todos.iter().filter(|t| t.parent == Some(current)) {
+                // 잘못된 데이터로 순환이 생겨도 무한 반복하지 않도록 방문 여부 확인
+                if child.id != id && !result.contains(&child.id) {
+                    result.push(child.id);
+                    stack.push(child.id);
+                }
+            }
+        }
+        result
+    }
+
+    // 특정 ID의 할일 완료 상태 토글
+    // 완료/취소된 할일은 "할 일"로, 나머지는 "완료"로 변경
+    // with_children가 true이면 묻지 않고 미완료 하위 할일도 함께 완료
+    pub fn toggle(&mut self, id: usize, with_children: bool) -> Result<(), Box<dyn Error>> {
+        // iter_mut()로 가변 반복자를 얻어 직접 수정
+        match self.todos.iter_mut().find(|t| t.id == id) {
+            Some(todo) => {
+                todo.toggle();
+                self.after_status_change(id, with_children)
+            }
+            None => {
+                // ID를 찾을 수 없는 경우
+                println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+                Ok(())
+            }
+        }
+    }
+
+    // 특정 ID의 할일 진행 상태 변경
+    pub fn set_status(&mut self, id: usize, status: Status) -> Result<(), Box<dyn Error>> {
+        match self.todos.iter_mut().find(|t| t.id == id) {
+            Some(todo) => {
+                todo.set_status(status);
+                self.after_status_change(id, false)
+            }
+            None => {
+                println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+                Ok(())
+            }
+        }
+    }
+
+    // 상태 변경 후의 공통 처리
+    // 완료/취소되면 타이머를 멈추고, 완료되면 하위 할일 처리와 반복 할일 생성까지 수행
+    fn after_status_change(&mut self, id: usize, with_children: bool) -> Result<(), Box<dyn Error>> {
+        // 완료한 할일의 선행 할일이 아직 끝나지 않았으면 경고
+        if let Some(todo) = self.todos.iter().find(|t| t.id == id && t.status == Status::Done) {
+            let blockers = self.open_dependencies(todo);
+            if !blockers.is_empty() {
+                println!(
+                    "{}",
+                    format!("⚠️  아직 끝나지 않은 선행 할 일이 있습니다: {}", id_list(blockers)).yellow()
+                );
+            }
+        }
+
+        let Some(todo) = self.todos.iter_mut().find(|t| t.id == id) else {
+            return Ok(());
+        };
+        let status = todo.status;
+
+        // 상태에 따른 메시지 출력
+        let msg = match status {
+            Status::Done => format!("✅ 완료: {}", todo.title).green(),
+            Status::Cancelled => format!("✗ 취소: {}", todo.title).dimmed(),
+            Status::InProgress => format!("▶ 진행 중: {}", todo.title).green(),
+            Status::Blocked => format!("■ 막힘: {}", todo.title).magenta(),
+            Status::Todo => format!("⏸️  미완료로 변경: {}", todo.title).yellow(),
+        };
+        println!("{}", msg);
+
+        // 완료/취소하면서 타이머가 돌고 있었다면 함께 정지
+        if status.is_closed() && let Some(elapsed) = todo.stop_timer(Local::now()) {
+            println!("{}", format!("⏹️  타이머 정지 ({})", dates::format_duration(elapsed)).cyan());
+        }
+
+        // 완료한 할일에 미완료 하위 할일이 남아 있으면 함께 완료할지 확인
+        if status == Status::Done {
+            let open: Vec<usize> = self
+                .descendants(id)
+                .into_iter()
+                .filter(|child| self.todos.iter().any(|t| t.id == *child && !t.is_closed()))
+                .collect();
+            if !open.is_empty()
+                && (with_children || self.ask(&format!("하위 할 일 {}개도 완료할까요?", open.len()))?)
+            {
+                let now = Local::now();
+                for todo in self.todos.iter_mut().filter(|t| open.contains(&t.id) && !t.is_closed()) {
+                    todo.set_status(Status::Done);
+                    todo.stop_timer(now);
+                    println!("{}", format!("   ✅ 완료: {}", todo.title).green());
+                }
+                for child in open {
+                    self.spawn_next_occurrence(child);
+                }
+            }
+
+            // 반복 할일이면 다음 회차 생성
+            self.spawn_next_occurrence(id);
+        }
+
+        // 변경사항 저장
+        self.save()?;
+        Ok(())
+    }
+
+    // 완료된 반복 할일의 다음 회차를 새 ID로 추가
+    // 반복 규칙은 새 할일로 옮겨지므로 같은 할일에서 두 번 생성되지 않음
+    fn spawn_next_occurrence(&mut self, id: usize) {
+        let next_id = self.next_id;
+        let next = self
+            .todos
+            .iter_mut()
+            .find(|t| t.id == id)
+            .and_then(|todo| todo.next_occurrence(next_id, Local::now()));
+
+        if let Some(next) = next {
+            let due = next.due.map(|d| d.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default();
+            println!("{}", format!("🔁 다음 반복 추가: [{}] {} (마감: {})", next.id, next.title, due).cyan());
+            self.todos.push(next);
+            self.next_id += 1;
+        }
+    }
+
+    // 반복 규칙 설정 (None이면 반복 해제)
+    pub fn set_recurrence(&mut self, id: usize, recurrence: Option<Recurrence>) -> Result<(), Box<dyn Error>> {
+        match self.todos.iter_mut().find(|t| t.id == id) {
+            Some(todo) => {
+                let msg = match &recurrence {
+                    Some(rule) => format!("🔁 반복 설정: {} ({})", todo.title, rule),
+                    None => format!("🔁 반복 해제: {}", todo.title),
+                };
+                todo.set_recurrence(recurrence);
+                println!("{}", msg.green());
+                self.save()?;
+            }
+            None => {
+                println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            }
+        }
+        Ok(())
+    }
+
+    // 특정 ID의 할일 제목/우선순위/프로젝트 수정
+    // Option으로 받아서 지정된 항목만 변경
+    pub fn edit(&mut self, id: usize, options: EditOptions) -> Result<(), Box<dyn Error>> {
+        // 상위 할일 변경은 자기 자신이나 자신의 하위 할일 아래로 옮길 수 없음
+        if let Some(parent) = options.parent.filter(|p| *p != 0) {
+            if !self.todos.iter().any(|t| t.id == parent) {
+                println!("{}", format!("❌ 상위 할 일 ID {} 를 찾을 수 없습니다.", parent).red());
+                return Ok(());
+            }
+            if parent == id || self.descendants(id).contains(&parent) {
+                println!("{}", "❌ 자기 자신이나 하위 할 일 아래로 옮길 수 없습니다.".red());
+                return Ok(());
+            }
+        }
+
+        match self.todos.iter_mut().find(|t| t.id == id) {
+            Some(todo) => {
+                if let Some(title) = options.title {
+                    todo.update_title(title);
+                }
+                if let Some(priority) = options.priority {
+                    todo.set_priority(priority);
+                }
+                if let Some(project) = options.project {
+                    todo.set_project(Some(&project));
+                }
+                if let Some(parent) = options.parent {
+                    todo.set_parent((parent != 0).then_some(parent));
+                }
+                println!("{}", format!("✏️  수정됨: {}", todo).green());
+                self.save()?;
+            }
+            None => {
+                println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            }
+        }
+        Ok(())
+    }
+
+    // 특정 ID의 할일 마감 기한 설정 (None이면 기한 제거)
+    pub fn set_due(&mut self, id: usize, due: Option<DateTime<Local>>) -> Result<(), Box<dyn Error>> {
+        match self.todos.iter_mut().find(|t| t.id == id) {
+            Some(todo) => {
+                todo.set_due(due);
+                let msg = match due {
+                    Some(due) => format!("📅 마감 기한 설정: {} ({})", todo.title, due.format("%Y-%m-%d %H:%M")),
+                    None => format!("📅 마감 기한 제거: {}", todo.title),
+                };
+                println!("{}", msg.green());
+                self.save()?;
+            }
+            None => {
+                println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            }
+        }
+        Ok(())
+    }
+
+    // 태그 추가/제거
+    // "+태그"는 추가, "-태그"는 제거, 기호가 없으면 추가로 처리
+    pub fn tag(&mut self, id: usize, changes: &[String]) -> Result<(), Box<dyn Error>> {
+        match self.todos.iter_mut().find(|t| t.id == id) {
+            Some(todo) => {
+                for change in changes {
+                    if let Some(tag) = change.strip_prefix('-') {
+                        if todo.remove_tag(tag) {
+                            println!("{}", format!("🏷️  태그 제거: #{}", tag).yellow());
+                        }
+                    } else {
+                        let tag = change.strip_prefix('+').unwrap_or(change);
+                        if todo.add_tag(tag) {
+                            println!("{}", format!("🏷️  태그 추가: #{}", tag).green());
+                        }
+                    }
+                }
+                println!("{}", todo);
+                self.save()?;
+            }
+            None => {
+                println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            }
+        }
+        Ok(())
+    }
+
+    // 사용 중인 모든 태그와 태그별 미완료/완료 개수 출력
+    pub fn tags(&self) {
+        // 태그 이름 → (미완료 수, 완료 수)
+        let mut counts: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+        for todo in &self.todos {
+            for tag in &todo.tags {
+                let entry = counts.entry(tag.as_str()).or_default();
+                if todo.is_closed() {
+                    entry.1 += 1;
+                } else {
+                    entry.0 += 1;
+                }
+            }
+        }
+
+        if counts.is_empty() {
+            println!("{}", "🏷️  사용 중인 태그가 없습니다.".yellow());
+            return;
+        }
+
+        println!("{}", "\n🏷️  태그 목록:".bold());
+        println!("{}", "─".repeat(30));
+        for (tag, (open, done)) in counts {
+            println!("#{:<15} 미완료: {:>3} | 완료: {:>3}", tag, open, done);
+        }
+    }
+
+    // 특정 ID의 할일 삭제
+    // cascade가 true이면 하위 할일도 모두 삭제하고,
+    // 아니면 하위 할일을 삭제된 할일의 상위 할일 아래로 옮김
+    pub fn delete(&mut self, id: usize, cascade: bool) -> Result<(), Box<dyn Error>> {
+        // position()으로 인덱스를 찾고 remove()로 삭제
+        if let Some(pos) = self.todos.iter().position(|t| t.id == id) {
+            let descendants = if cascade { self.descendants(id) } else { Vec::new() };
+            let removed = self.todos.remove(pos);
+            println!("{}", format!("🗑️  휴지통으로 이동: {} (되돌리려면: todo restore {})", removed.title, removed.id).red());
+            let mut trashed = vec![removed.clone()];
+
+            if cascade {
+                // 하위 할일까지 모두 휴지통으로
+                let (children, rest): (Vec<Todo>, Vec<Todo>) =
+                    std::mem::take(&mut self.todos).into_iter().partition(|t| descendants.contains(&t.id));
+                self.todos = rest;
+                trashed.extend(children);
+                if !descendants.is_empty() {
+                    println!("{}", format!("🗑️  하위 할 일 {}개도 휴지통으로 옮겼습니다.", descendants.len()).red());
+                }
+            } else {
+                // 남은 하위 할일은 한 단계 위로 올림
+                let removed_parents = HashMap::from([(removed.id, removed.parent)]);
+                reattach_orphans(&mut self.todos, &removed_parents);
+            }
+
+            // 삭제된 할일에 대한 의존 관계 정리
+            let remaining: HashSet<usize> = self.todos.iter().map(|t| t.id).collect();
+            for todo in &mut self.todos {
+                todo.depends_on.retain(|dep| remaining.contains(dep));
+            }
+
+            // 휴지통을 먼저 저장해야 중간에 실패해도 할일이 사라지지 않음
+            let now = Local::now();
+            let mut trash = self.backend.load_todos(Collection::Trash)?;
+            trash.extend(trashed.into_iter().map(|mut todo| {
+                todo.deleted_at = Some(now);
+                todo
+            }));
+            self.backend.save(Collection::Trash, &trash, None)?;
+            self.save()?;
+        } else {
+            println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+        }
+        Ok(())
+    }
+
+    // 할일의 순서를 옮김 (ID는 그대로, 순서만 바뀜)
+    pub fn move_todo(&mut self, id: usize, placement: Placement) -> Result<(), Box<dyn Error>> {
+        let Some(from) = self.todos.iter().position(|t| t.id == id) else {
+            println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", id).red());
+            return Ok(());
+        };
+        if let Placement::Before(target) = placement {
+            if target == id {
+                println!("{}", "❌ 자기 자신의 앞으로는 옮길 수 없습니다.".red());
+                return Ok(());
+            }
+            if !self.todos.iter().any(|t| t.id == target) {
+                println!("{}", format!("❌ ID {} 를 찾을 수 없습니다.", target).red());
+                return Ok(());
+            }
+        }
+
+        // 목록은 항상 순서대로 정렬되어 있으므로 꺼내서 원하는 자리에 다시 넣음
+        let todo = self.todos.remove(from);
+        let to = match placement {
+            Placement::Before(target) => self.todos.iter().position(|t| t.id == target).unwrap_or(0),
+            Placement::Top => 0,
+            Placement::Bottom => self.todos.len(),
+        };
+        let title = todo.title.clone();
+        self.todos.insert(to, todo);
+        renumber_positions(&mut self.todos);
+
+        let msg = match placement {
+            Placement::Before(target) => format!("↕️  [{}] {} → [{}] 앞으로 이동", id, title, target),
+            Placement::Top => format!("⬆️  [{}] {} → 맨 위로 이동", id, title),
+            Placement::Bottom => format!("⬇️  [{}] {} → 맨 아래로 이동", id, title),
+        };
+        println!("{}", msg.green());
+
+        // 기본 목록은 우선순위 순이라, 정한 순서가 우선순위와 어긋나면 그대로 보이지 않음
+        if !self.todos.is_sorted_by_key(|t| t.priority) {
+            println!("{}", "💡 기본 목록은 우선순위 순이라 같은 우선순위 안에서만 이 순서를 따릅니다. 정한 순서대로 보려면: list --sort manual".dimmed());
+        }
+        self.save()?;
+        Ok(())
+    }
+
+    // 완료/취소된 모든 할일을 보관함으로 옮김
+    pub fn clear_completed(&mut self) -> Result<(), Box<dyn Error>> {
+        // 옮겨질 할일의 상위 할일을 기억해 두었다가 남은 하위 할일을 다시 연결
+        let removed_parents: HashMap<usize, Option<usize>> = self
+            .todos
+            .iter()
+            .filter(|t| t.is_closed())
+            .map(|t| (t.id, t.parent))
+            .collect();
+        if removed_parents.is_empty() {
+            println!("{}", "완료/취소된 할 일이 없습니다.".yellow());
+            return Ok(());
+        }
+
+        // partition()으로 닫힌 항목과 아직 닫히지 않은 항목을 나눔
+        let (closed, open): (Vec<Todo>, Vec<Todo>) =
+            std::mem::take(&mut self.todos).into_iter().partition(|t| t.is_closed());
+        self.todos = open;
+        reattach_orphans(&mut self.todos, &removed_parents);
+
+        // 보관된 (완료/취소된) 할일에 대한 의존은 더 이상 기다릴 필요가 없으므로 제거
+        for todo in &mut self.todos {
+            todo.depends_on.retain(|dep| !removed_parents.contains_key(dep));
+        }
+
+        // 보관함을 먼저 저장해야 중간에 실패해도 할일이 사라지지 않음
+        let now = Local::now();
+        let moved = closed.len();
+        let mut archived = self.backend.load_todos(Collection::Archive)?;
+        archived.extend(closed.into_iter().map(|mut todo| {
+            todo.archived_at = Some(now);
+            todo
+        }));
+        self.backend.save(Collection::Archive, &archived, None)?;
+        self.save()?;
+
+        println!("{}", format!("🗄️  {} 개의 완료/취소된 할 일을 보관함으로 옮겼습니다. (todo archive list 로 보기)", moved).cyan());
+        Ok(())
+    }
+
+    // 보관함의 할일 목록 출력 (최근에 보관된 순)
+    pub fn archive_list(&self) -> Result<(), Box<dyn Error>> {
+        let archived = self.backend.load_todos(Collection::Archive)?;
+        print_removed(archived.iter().collect(), "🗄️  보관함", "보관", |t| t.archived_at, "🗄️  보관함이 비어 있습니다.");
+        Ok(())
+    }
+
+    // 보관함에서 제목, 메모, 태그, 프로젝트에 검색어가 들어 있는 할일 출력 (대소문자 무시)
+    pub fn archive_search(&self, query: &str) -> Result<(), Box<dyn Error>> {
+        let query = query.to_lowercase();
+        let contains = |text: &str| text.to_lowercase().contains(&query);
+
+        let archived = self.backend.load_todos(Collection::Archive)?;
+        let found: Vec<&Todo> = archived
+            .iter()
+            .filter(|t| {
+                contains(&t.title)
+                    || t.notes.as_deref().is_some_and(contains)
+                    || t.tags.iter().any(|tag| contains(tag))
+                    || t.project.as_deref().is_some_and(contains)
+            })
+            .collect();
+        print_removed(found, "🗄️  보관함", "보관", |t| t.archived_at, "🔍 보관함에서 찾은 할 일이 없습니다.");
+        Ok(())
+    }
+
+    // 보관함의 할일을 다시 목록으로 복원
+    pub fn archive_restore(&mut self, id: usize) -> Result<(), Box<dyn Error>> {
+        let mut archived = self.backend.load_todos(Collection::Archive)?;
+        let Some(pos) = archived.iter().position(|t| t.id == id) else {
+            println!("{}", format!("❌ 보관함에서 ID {} 를 찾을 수 없습니다.", id).red());
+            return Ok(());
+        };
+        let mut todo = archived.remove(pos);
+
+        // 보관된 동안 다른 할일이 같은 ID를 쓰게 되었으면 새 ID를 부여
+        if self.todos.iter().any(|t| t.id == todo.id) {
+            println!("{}", format!("ID {} 는 이미 사용 중이라 새 ID {} 로 복원합니다.", todo.id, self.next_id).yellow());
+            todo.id = self.next_id;
+            self.next_id += 1;
+        }
+        println!("{}", format!("♻️  보관함에서 복원: [{}] {}", todo.id, todo.title).green());
+        self.reinsert(todo);
+
+        // 목록을 먼저 저장해야 중간에 실패해도 할일이 사라지지 않음
+        self.save()?;
+        self.backend.save(Collection::Archive, &archived, None)?;
+        Ok(())
+    }
+
+    // 휴지통의 할일 목록 출력 (최근에 삭제된 순)
+    pub fn trash_list(&self) -> Result<(), Box<dyn Error>> {
+        let trashed = self.backend.load_todos(Collection::Trash)?;
+        print_removed(trashed.iter().collect(), "🗑️  휴지통", "삭제", |t| t.deleted_at, "🗑️  휴지통이 비어 있습니다.");
+        Ok(())
+    }
+
+    // 휴지통의 할일을 원래 ID 그대로 다시 목록으로 복원
+    pub fn restore(&mut self, id: usize) -> Result<(), Box<dyn Error>> {
+        let mut trashed = self.backend.load_todos(Collection::Trash)?;
+        let Some(pos) = trashed.iter().position(|t| t.id == id) else {
+            println!("{}", format!("❌ 휴지통에서 ID {} 를 찾을 수 없습니다.", id).red());
+            return Ok(());
+        };
+        // 지운 할일의 ID는 다시 쓰지 않으므로 파일을 직접 고치지 않았다면 겹치지 않음
+        if self.todos.iter().any(|t| t.id == id) {
+            println!("{}", format!("❌ ID {} 는 이미 다른 할 일이 사용 중입니다.", id).red());
+            return Ok(());
+        }
+
+        let todo = trashed.remove(pos);
+        println!("{}", format!("♻️  휴지통에서 복원: [{}] {}", todo.id, todo.title).green());
+        self.reinsert(todo);
+
+        // 목록을 먼저 저장해야 중간에 실패해도 할일이 사라지지 않음
+        self.save()?;
+        self.backend.save(Collection::Trash, &trashed, None)?;
+        Ok(())
+    }
+
+    // 휴지통 비우기 (older_than이 있으면 그보다 오래 전에 삭제된 할일만)
+    // 더 이상 쓰이지 않는 첨부 파일도 함께 정리
+    pub fn purge_trash(&mut self, older_than: Option<Duration>) -> Result<(), Box<dyn Error>> {
+        let mut trashed = self.backend.load_todos(Collection::Trash)?;
+        let before_count = trashed.len();
+        if let Some(age) = older_than {
+            let cutoff = Local::now() - age;
+            trashed.retain(|t| t.deleted_at.is_some_and(|at| at > cutoff));
+        } else {
+            trashed.clear();
+        }
+
+        let purged = before_count - trashed.len();
+        if purged == 0 {
+            println!("{}", "휴지통에서 비울 할 일이 없습니다.".yellow());
+            return Ok(());
+        }
+        self.backend.save(Collection::Trash, &trashed, None)?;
+        println!("{}", format!("🔥 휴지통에서 {} 개의 할 일을 영구 삭제했습니다.", purged).red());
+        self.collect_attachment_garbage()?;
+        Ok(())
+    }
+
+    // 보관함이나 휴지통에서 꺼낸 할일을 목록 맨 뒤에 다시 추가
+    // 더 이상 목록에 없는 상위/선행 할일과의 연결은 끊음
+    fn reinsert(&mut self, mut todo: Todo) {
+        if let Some(parent) = todo.parent
+            && !self.todos.iter().any(|t| t.id == parent)
+        {
+            todo.parent = None;
+        }
+        todo.depends_on.retain(|dep| self.todos.iter().any(|t| t.id == *dep));
+        todo.position = self.todos.iter().map(|t| t.position).max().unwrap_or(0) + 1;
+        todo.archived_at = None;
+        todo.deleted_at = None;
+        todo.updated_at = Local::now();
+        self.todos.push(todo);
+    }
+
+    // 프로젝트를 트리 형태로 출력하고 프로젝트별 완료율 표시
+    // 상위 프로젝트의 집계에는 하위 프로젝트의 할일이 모두 포함됨
+    pub fn projects(&self) {
+        // 프로젝트 경로(구간 목록) → (전체 수, 완료 수)
+        // 구간 단위로 비교하여 정렬하므로 하위 프로젝트가 항상 부모 바로 아래에 옴
+        let mut counts: BTreeMap<Vec<&str>, (usize, usize)> = BTreeMap::new();
+        for todo in &self.todos {
+            let Some(project) = &todo.project else {
+                continue;
+            };
+            let segments: Vec<&str> = project.split('.').collect();
+            // work.backend.api 라면 work, work.backend, work.backend.api 모두에 집계
+            for depth in 1..=segments.len() {
+                let entry = counts.entry(segments[..depth].to_vec()).or_default();
+                entry.0 += 1;
+                if todo.is_closed() {
+                    entry.1 += 1;
+                }
+            }
+        }
+
+        if counts.is_empty() {
+            println!("{}", "📁 프로젝트가 없습니다.".yellow());
+            return;
+        }
+
+        println!("{}", "\n📁 프로젝트:".bold());
+        println!("{}", "─".repeat(50));
+        for (segments, (total, done)) in &counts {
+            let indent = "  ".repeat(segments.len() - 1);
+            let name = segments.last().copied().unwrap_or_default();
+            let percentage = (*done as f64 / *total as f64 * 100.0) as u32;
+            // 들여쓴 만큼 이름 칸을 줄여서 프로그레스 바 위치를 맞춤
+            let width = 20usize.saturating_sub(indent.len());
+            println!(
+                "{}{:<width$} {} {:>3}% ({}/{})",
+                indent,
+                name,
+                progress_bar(percentage, 10),
+                percentage,
+                done,
+                total
+            );
+        }
+
+        // 프로젝트가 지정되지 않은 할일 수
+        let unassigned = self.todos.iter().filter(|t| t.project.is_none()).count();
+        if unassigned > 0 {
+            println!("{}", format!("(프로젝트 없음: {})", unassigned).dimmed());
+        }
+    }
+
+    // 통계 정보 출력
+    pub fn stats(&self) {
+        let total = self.todos.len();
+        let completed = self.todos.iter().filter(|t| t.is_closed()).count();
+        let pending = total - completed;
+
+        // 통계 헤더
+        println!("{}", "\n📊 통계:".bold());
+        println!("{}", "─".repeat(30));
+        
+        // 기본 통계
+        println!("전체 할 일: {}", total);
+        println!("완료됨: {} {}", completed, "✅".repeat(completed.min(10)));
+        println!("미완료: {} {}", pending, "⏳".repeat(pending.min(10)));
+        
+        // 진행률 표시
+        if total > 0 {
+            let percentage = (completed as f64 / total as f64 * 100.0) as u32;
+            println!("완료율: {}%", percentage);
+            
+            // 프로그레스 바 출력
+            println!("{}", progress_bar(percentage, 20));
+        }
+
+        // 상태별 할일 수
+        println!("{}", "\n📌 상태별:".bold());
+        for status in Status::ALL {
+            let count = self.todos.iter().filter(|t| t.status == status).count();
+            println!("{} {}: {}", status.symbol(), status.label(), count);
+        }
+
+        // 마감 기한 관련 통계
+        let now = Local::now();
+        let overdue = self.todos.iter().filter(|t| t.is_overdue(now)).count();
+        let due_today = self.todos.iter().filter(|t| t.is_due_today(now)).count();
+        println!("{}", "\n📅 마감 기한:".bold());
+        println!("{}", format!("기한 지남: {}", overdue).red());
+        println!("{}", format!("오늘 마감: {}", due_today).yellow());
+        let waiting = self.todos.iter().filter(|t| !t.is_closed() && t.is_waiting(now)).count();
+        println!("{}", format!("대기 중 (숨김): {}", waiting).dimmed());
+
+        // 시간 기록 통계 (기록이 있는 할일만, 오래 걸린 순)
+        let mut tracked: Vec<(&Todo, Duration)> = self
+            .todos
+            .iter()
+            .filter(|t| !t.time_entries.is_empty())
+            .map(|t| (t, t.tracked_time(now)))
+            .collect();
+        if !tracked.is_empty() {
+            tracked.sort_by_key(|(_, duration)| std::cmp::Reverse(*duration));
+            let total: Duration = tracked.iter().map(|(_, duration)| *duration).sum();
+            println!("{}", "\n⏱️  시간 기록:".bold());
+            for (todo, duration) in &tracked {
+                let running = if todo.is_tracking() { " ⏱️" } else { "" };
+                println!("[{}] {}: {}{}", todo.id, todo.title, dates::format_duration(*duration), running);
+            }
+            println!("{}", format!("총 기록 시간: {}", dates::format_duration(total)).cyan());
+        }
+
+        // 우선순위별 미완료 할일 수
+        println!("{}", "\n🔥 우선순위별 미완료:".bold());
+        for priority in [Priority::High, Priority::Medium, Priority::Low, Priority::None] {
+            let count = self
+                .todos
+                .iter()
+                .filter(|t| !t.is_closed() && t.priority == priority)
+                .count();
+            println!("{}: {}", priority.label(), count);
+        }
+    }
+}
+
+// 목록 정렬 기준
+enum SortKey {
+    Priority,                            // 우선순위 (기본)
+    Due,                                 // 마감 기한 (없는 것은 뒤로)
+    Created,                             // 생성 시간
+    Id,                                  // ID
+    Title,                               // 제목
+    Manual,                              // move 명령으로 정한 순서
+    Field(String),                       // 사용자 정의 필드 (값이 없는 것은 뒤로)
+}
+
+impl SortKey {
+    // 정렬 기준 이름을 파싱 (기본 기준이 아니면 사용자 정의 필드에서 찾음)
+    fn parse(name: &str, config: &Config) -> Result<Self, String> {
+        match name {
+            "priority" => Ok(SortKey::Priority),
+            "due" => Ok(SortKey::Due),
+            "created" => Ok(SortKey::Created),
+            "id" => Ok(SortKey::Id),
+            "title" => Ok(SortKey::Title),
+            "manual" => Ok(SortKey::Manual),
+            _ => fields::find(&config.fields, name)
+                .map(|def| SortKey::Field(def.name.clone()))
+                .map_err(|_| {
+                    format!(
+                        "알 수 없는 정렬 기준입니다: '{}' (priority, due, created, id, title, manual 또는 필드 이름)",
+                        name
+                    )
+                }),
+        }
+    }
+
+    // 두 할일을 이 기준으로 비교
+    fn compare(&self, a: &Todo, b: &Todo) -> Ordering {
+        // 값이 없는 쪽을 뒤로 보내는 Option 비교
+        fn none_last<T>(a: Option<T>, b: Option<T>, cmp: impl Fn(&T, &T) -> Ordering) -> Ordering {
+            match (a, b) {
+                (Some(a), Some(b)) => cmp(&a, &b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        }
+
+        match self {
+            SortKey::Priority => a.priority.cmp(&b.priority),
+            SortKey::Due => none_last(a.due, b.due, |a, b| a.cmp(b)),
+            SortKey::Created => a.created_at.cmp(&b.created_at),
+            SortKey::Id => a.id.cmp(&b.id),
+            SortKey::Title => a.title.cmp(&b.title),
+            SortKey::Manual => a.position.cmp(&b.position),
+            SortKey::Field(name) => none_last(a.fields.get(name), b.fields.get(name), |a, b| a.compare(b)),
+        }
+    }
+}
+
+// 보관함/휴지통의 할일들을 최근에 옮겨진 순으로 출력 (비어 있으면 empty 메시지)
+// removed_at: 보관/삭제된 시각, label: 그 시각 앞에 붙일 이름 ("보관", "삭제")
+fn print_removed(
+    mut todos: Vec<&Todo>,
+    header: &str,
+    label: &str,
+    removed_at: fn(&Todo) -> Option<DateTime<Local>>,
+    empty: &str,
+) {
+    if todos.is_empty() {
+        println!("{}", empty.yellow());
+        return;
+    }
+
+    todos.sort_by(|a, b| removed_at(b).cmp(&removed_at(a)).then(a.id.cmp(&b.id)));
+    println!("{}", format!("\n{}:", header).bold());
+    println!("{}", "─".repeat(50));
+    for todo in &todos {
+        let at = removed_at(todo)
+            .map(|at| format!(" ({}: {})", label, at.format("%Y-%m-%d %H:%M")))
+            .unwrap_or_default();
+        println!("{}{}", format!("{}", todo).dimmed(), at.dimmed());
+    }
+    println!("{}", "─".repeat(50));
+    println!("{}", format!("전체: {}", todos.len()).cyan());
+}
+
+// 체크리스트 항목 한 줄 (번호는 check toggle/remove에 사용)
+fn check_line(n: usize, item: &CheckItem) -> String {
+    if item.done {
+        format!("{}. ☑ {}", n, item.text).dimmed().to_string()
+    } else {
+        format!("{}. ☐ {}", n, item.text)
+    }
+}
+
+// 번호 또는 UUID 앞부분(대소문자 무시)으로 할일 번호를 찾음
+// 숫자만으로 된 값은 항상 번호로 봄 (없는 번호는 각 명령이 "찾을 수 없음"을 알림)
+pub fn resolve_key(todos: &[Todo], key: &str) -> Result<usize, String> {
+    if let Ok(id) = key.parse::<usize>() {
+        return Ok(id);
+    }
+
+    let prefix = key.to_lowercase();
+    if prefix.is_empty() || !prefix.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+        return Err(format!("'{}' 는 번호나 UUID 앞부분이 아닙니다.", key));
+    }
+    let matches: Vec<usize> = todos
+        .iter()
+        .filter(|t| t.uuid.to_string().starts_with(&prefix))
+        .map(|t| t.id)
+        .collect();
+
+    match matches.as_slice() {
+        [id] => Ok(*id),
+        [] => Err(format!("UUID가 '{}' 로 시작하는 할 일을 찾을 수 없습니다.", key)),
+        ids => Err(format!(
+            "UUID가 '{}' 로 시작하는 할 일이 여러 개입니다: {} (더 길게 입력하세요)",
+            key,
+            id_list(ids.iter().copied())
+        )),
+    }
+}
+
+// 두 todos.json을 이어 붙인 목록을 정리한 결과 - (하나로 합친 UUID 목록, 새 번호를 받은 (UUID, 새 번호) 목록)
+pub type Merged = (Vec<uuid::Uuid>, Vec<(uuid::Uuid, usize)>);
+
+// 여러 파일을 이어 붙인 것처럼 같은 할일이나 같은 번호가 다시 나오는 목록을 정리
+// - 같은 UUID가 다시 나오면 같은 할일이므로 하나로 합침 (더 최근에 수정된 내용을 앞쪽 자리에 남김)
+// - UUID가 다른데 번호가 겹치면 뒤쪽 할일에 next_id부터 새 번호를 부여
+// 번호가 다시 나오는 곳부터를 다음 파일로 보고, 각 파일 안의 parent/depends_on을 바뀐 번호로 고침
+pub fn merge_duplicates(todos: &mut Vec<Todo>, mut next_id: usize) -> Merged {
+    // 파일 경계로 나누기 - 한 파일 안에서는 번호가 겹치지 않음
+    let mut segments: Vec<Vec<Todo>> = Vec::new();
+    let mut segment_ids = HashSet::new();
+    for todo in todos.drain(..) {
+        if segments.is_empty() || !segment_ids.insert(todo.id) {
+            segment_ids = HashSet::from([todo.id]);
+            segments.push(Vec::new());
+        }
+        segments.last_mut().expect("방금 추가한 묶음").push(todo);
+    }
+
+    let mut merged = Vec::new();
+    let mut renumbered = Vec::new();
+    let mut index_of: HashMap<uuid::Uuid, usize> = HashMap::new(); // UUID → todos 안의 위치
+    let mut used_ids = HashSet::new();
+    for segment in segments {
+        let mut remap: HashMap<usize, usize> = HashMap::new(); // 이 파일의 번호 → 합친 뒤 번호
+        let mut members = Vec::new();                          // 이 파일의 참조를 따르는 할일 위치
+        for mut todo in segment {
+            if let Some(&i) = index_of.get(&todo.uuid) {
+                remap.insert(todo.id, todos[i].id);
+                if todo.updated_at > todos[i].updated_at {
+                    todo.id = todos[i].id;
+                    todo.position = todos[i].position;
+                    todos[i] = todo;
+                    members.push(i);
+                }
+                merged.push(todos[i].uuid);
+                continue;
+            }
+            let old_id = todo.id;
+            if !used_ids.insert(todo.id) {
+                todo.id = next_id;
+                next_id += 1;
+                used_ids.insert(todo.id);
+                renumbered.push((todo.uuid, todo.id));
+            }
+            remap.insert(old_id, todo.id);
+            index_of.insert(todo.uuid, todos.len());
+            members.push(todos.len());
+            todos.push(todo);
+        }
+
+        // 이 파일의 할일이 가리키던 번호를 합친 뒤 번호로 바꿈
+        for i in members {
+            let todo = &mut todos[i];
+            todo.parent = todo.parent.map(|p| remap.get(&p).copied().unwrap_or(p));
+            todo.depends_on = todo.depends_on.iter().map(|d| remap.get(d).copied().unwrap_or(*d)).collect();
+        }
+    }
+    (merged, renumbered)
+}
+
+// 저장된 순서(position)대로 목록을 정렬하고 1부터 다시 번호를 매김
+// 순서가 없던 예전 todos.json은 모두 0이므로 안정 정렬로 파일에 있던 순서가 그대로 유지됨
+pub fn normalize_positions(todos: &mut [Todo]) {
+    todos.sort_by_key(|t| t.position);
+    renumber_positions(todos);
+}
+
+// 현재 벡터 순서대로 position을 1, 2, 3... 으로 다시 매김
+fn renumber_positions(todos: &mut [Todo]) {
+    for (i, todo) in todos.iter_mut().enumerate() {
+        todo.position = i + 1;
+    }
+}
+
+// 삭제된 할일을 가리키는 하위 할일들을 살아남은 가장 가까운 상위 할일에 다시 연결
+// removed: 삭제된 할일 ID → 그 할일의 상위 할일 ID
+pub fn reattach_orphans(todos: &mut [Todo], removed: &HashMap<usize, Option<usize>>) {
+    for todo in todos.iter_mut() {
+        let mut parent = todo.parent;
+        // 상위 할일도 삭제되었다면 계속 위로 올라감
+        while let Some(removed_parent) = parent.and_then(|p| removed.get(&p)) {
+            parent = *removed_parent;
+        }
+        todo.parent = parent;
+    }
+}
+
+// id가 dep에 의존하도록 추가했을 때 순환이 생기는지 확인
+// dep에서 의존 관계를 따라가다 id에 도달하면 순환
+pub fn creates_cycle(todos: &[Todo], id: usize, dep: usize) -> bool {
+    let mut stack = vec![dep];
+    let mut visited = HashSet::new();
+    while let Some(current) = stack.pop() {
+        if current == id {
+            return true;
+        }
+        if !visited.insert(current) {
+            continue;
+        }
+        if let Some(todo) = todos.iter().find(|t| t.id == current) {
+            stack.extend(todo.depends_on.iter().copied());
+        }
+    }
+    false
+}
+
+// 사용자에게 y/N 확인을 받음
+// 터미널이 아닌 경우(스크립트, 파이프)에는 묻지 않고 false
+fn confirm(question: &str) -> bool {
+    if !io::stdin().is_terminal() {
+        return false;
+    }
+    print!("{} [y/N] ", question.yellow());
+    // print!는 줄바꿈이 없어 버퍼에 남아 있으므로 직접 flush
+    if io::stdout().flush().is_err() {
+        return false;
+    }
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+// ID 목록을 "#1, #2" 형태의 문자열로 변환
+fn id_list(ids: impl IntoIterator<Item = usize>) -> String {
+    let ids: Vec<String> = ids.into_iter().map(|id| format!("#{}", id)).collect();
+    ids.join(", ")
+}
+
+// 완료율(0~100)을 주어진 길이의 프로그레스 바 문자열로 변환
+fn progress_bar(percentage: u32, bar_length: usize) -> String {
+    let filled = bar_length * percentage as usize / 100;
+    format!(
+        "[{}{}]",
+        "█".repeat(filled).green(),
+        "░".repeat(bar_length - filled).dimmed()
+    )
+}
+
+// 마감 기한 상태에 따라 목록 옆에 붙일 표시
+fn due_badge(todo: &Todo, now: DateTime<Local>) -> String {
+    if todo.is_overdue(now) {
+        format!(" {}", "⚠️ 기한 지남".red().bold())
+    } else if todo.is_due_today(now) {
+        format!(" {}", "⏰ 오늘 마감".yellow().bold())
+    } else if todo.is_due_within(now, Duration::days(DUE_SOON_DAYS)) {
+        format!(" {}", "곧 마감".yellow())
+    } else {
+        String::new()
+    }
+}
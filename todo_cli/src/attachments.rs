@@ -0,0 +1,102 @@
+// 첨부 파일 저장소 모듈
+// 파일은 내용의 해시를 이름으로 하여 todos.json 옆의 attachments 디렉터리에 복사됨
+// 같은 내용의 파일은 한 번만 저장되고, 어떤 할일도 참조하지 않으면 정리(GC)됨
+use sha2::{Digest, Sha256};   // 내용 기반 파일 이름을 만들기 위한 해시
+use std::collections::HashSet; // 참조 중인 파일 이름 집합
+use std::fs;                  // 파일 시스템 작업
+use std::io;                  // I/O 에러 타입
+use std::path::{Path, PathBuf}; // 파일 경로 처리
+use std::process::Command;    // 운영체제 기본 프로그램으로 열기
+
+// 저장 파일과 같은 위치에 만들 첨부 디렉터리 이름
+const ATTACHMENT_DIR: &str = "attachments";
+
+// 첨부 파일 저장소
+pub struct AttachmentStore {
+    dir: PathBuf,         // 첨부 파일을 보관하는 디렉터리
+}
+
+impl AttachmentStore {
+    // base_dir: todos.json이 있는 디렉터리
+    pub fn new(base_dir: &Path) -> Self {
+        Self {
+            dir: base_dir.join(ATTACHMENT_DIR),
+        }
+    }
+
+    // 파일을 저장소에 복사하고 저장된 파일 이름("해시.확장자")을 반환
+    // 이미 같은 내용이 있으면 복사하지 않음
+    pub fn store(&self, source: &Path) -> io::Result<String> {
+        let contents = fs::read(source)?;
+        let hash = Sha256::digest(&contents)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+
+        // 확장자를 남겨 두어야 기본 프로그램으로 열 때 파일 종류를 알 수 있음
+        let file = match source.extension().and_then(|e| e.to_str()) {
+            Some(ext) => format!("{}.{}", hash, ext.to_lowercase()),
+            None => hash,
+        };
+
+        let target = self.dir.join(&file);
+        if !target.exists() {
+            fs::create_dir_all(&self.dir)?;
+            fs::write(&target, contents)?;
+        }
+        Ok(file)
+    }
+
+    // 저장된 파일의 전체 경로
+    pub fn path(&self, file: &str) -> PathBuf {
+        self.dir.join(file)
+    }
+
+    // 참조되지 않는 첨부 파일을 삭제하고 삭제한 개수를 반환
+    pub fn collect_garbage(&self, referenced: &HashSet<&str>) -> io::Result<usize> {
+        if !self.dir.exists() {
+            return Ok(0);
+        }
+
+        let mut removed = 0;
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let in_use = name.to_str().is_some_and(|n| referenced.contains(n));
+            if !in_use && entry.file_type()?.is_file() {
+                fs::remove_file(entry.path())?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+// 링크로 저장할 URL인지 확인 (예: https://..., mailto:...)
+pub fn is_url(target: &str) -> bool {
+    match target.split_once("://") {
+        Some((scheme, _)) => !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c)),
+        None => target.starts_with("mailto:"),
+    }
+}
+
+// 파일이나 URL을 운영체제의 기본 프로그램으로 열기
+pub fn open(target: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        // start의 첫 번째 인자는 창 제목이므로 빈 문자열을 넘김
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+
+    let status = command.arg(target).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("열기에 실패했습니다: {}", status)))
+    }
+}
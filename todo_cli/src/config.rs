@@ -0,0 +1,36 @@
+// 사용자 설정 파일을 불러오는 모듈
+use crate::fields::FieldDef;  // 사용자 정의 필드 스키마
+use serde::Deserialize;       // JSON 역직렬화
+use std::fs;                  // 파일 읽기
+use std::io;                  // I/O 에러 타입
+use std::path::Path;          // 파일 경로 처리
+
+// 설정 파일명 - todos.json과 같은 위치에 둠
+const CONFIG_FILE: &str = "todo_config.json";
+
+// 설정 내용
+// 파일이 없거나 항목이 빠져 있으면 기본값 사용
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub fields: Vec<FieldDef>,           // 사용자 정의 필드 목록
+    #[serde(default)]
+    pub remind_command: Option<String>,  // 알림 시각에 실행할 명령 (없으면 터미널에 출력)
+}
+
+impl Config {
+    // 설정 파일 불러오기 (파일이 없으면 기본 설정)
+    pub fn load() -> Result<Self, io::Error> {
+        if !Path::new(CONFIG_FILE).exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(CONFIG_FILE)?;
+        serde_json::from_str(&contents).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} 을(를) 읽을 수 없습니다: {}", CONFIG_FILE, e),
+            )
+        })
+    }
+}
@@ -0,0 +1,115 @@
+// 날짜/시간 문자열 파싱을 담당하는 모듈
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+
+// 날짜만 입력했을 때 사용하는 시각 (그날이 끝나기 직전)
+const END_OF_DAY: (u32, u32) = (23, 59);
+
+// 사용자가 입력한 날짜 문자열을 로컬 시간으로 변환
+// 지원 형식:
+//   today, tomorrow        - 오늘/내일 (하루의 끝)
+//   +3d, +2w, +12h         - 지금으로부터 상대 시간
+//   2025-06-20             - 날짜 (하루의 끝)
+//   2025-06-20 14:30       - 날짜와 시각
+// clap의 value_parser로도 사용하기 위해 에러를 String으로 반환
+pub fn parse_datetime(input: &str) -> Result<DateTime<Local>, String> {
+    let input = input.trim();
+    let now = Local::now();
+
+    match input {
+        "today" | "오늘" => return end_of_day(now.date_naive()),
+        "tomorrow" | "내일" => return end_of_day(now.date_naive() + Duration::days(1)),
+        _ => {}
+    }
+
+    // +3d 같은 상대 시간
+    if let Some(rest) = input.strip_prefix('+') {
+        return Ok(now + parse_duration(rest)?);
+    }
+
+    // 날짜와 시각
+    if let Ok(naive) = NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M") {
+        return to_local(naive);
+    }
+
+    // 날짜만
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return end_of_day(date);
+    }
+
+    Err(format!(
+        "날짜 형식을 알 수 없습니다: '{}' (예: today, tomorrow, +3d, 2025-06-20, \"2025-06-20 14:30\")",
+        input
+    ))
+}
+
+// 시작일(대기) 문자열을 로컬 시간으로 변환
+// parse_datetime과 같지만 날짜만 쓰면 그날이 시작하는 0시로 해석하고,
+// "3d"처럼 +를 생략한 기간도 지금으로부터의 상대 시간으로 받음
+pub fn parse_start(input: &str) -> Result<DateTime<Local>, String> {
+    let input = input.trim();
+    if let "tomorrow" | "내일" = input {
+        return to_local((Local::now().date_naive() + Duration::days(1)).and_time(NaiveTime::MIN));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return to_local(date.and_time(NaiveTime::MIN));
+    }
+    if let Ok(duration) = parse_duration(input) {
+        return Ok(Local::now() + duration);
+    }
+    parse_datetime(input)
+}
+
+// "3d", "2w", "12h", "30m" 형태의 기간 문자열을 Duration으로 변환
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    // 마지막 글자가 단위, 나머지는 숫자 (한글처럼 여러 바이트인 글자도 글자 단위로 나눔)
+    let split = input.char_indices().last().map_or(0, |(i, _)| i);
+    let (number, unit) = input.split_at(split);
+    let amount: i64 = number
+        .parse()
+        .map_err(|_| format!("기간 형식을 알 수 없습니다: '{}' (예: 30m, 12h, 3d, 2w)", input))?;
+
+    match unit {
+        "m" => Ok(Duration::minutes(amount)),
+        "h" => Ok(Duration::hours(amount)),
+        "d" => Ok(Duration::days(amount)),
+        "w" => Ok(Duration::weeks(amount)),
+        _ => Err(format!("알 수 없는 기간 단위입니다: '{}' (m, h, d, w 중 하나)", unit)),
+    }
+}
+
+// 기간을 "1시간 23분" 형태의 문자열로 변환 (1분 미만은 초 단위)
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.num_seconds().max(0);
+    let (hours, minutes) = (seconds / 3600, seconds % 3600 / 60);
+    match (hours, minutes) {
+        (0, 0) => format!("{}초", seconds),
+        (0, m) => format!("{}분", m),
+        (h, m) => format!("{}시간 {}분", h, m),
+    }
+}
+
+// 날짜만 입력했을 때 사용하는 하루의 끝 시각
+pub fn end_of_day_time() -> NaiveTime {
+    let (hour, minute) = END_OF_DAY;
+    NaiveTime::from_hms_opt(hour, minute, 0).expect("유효한 시각")
+}
+
+// 특정 날짜의 특정 시각을 로컬 시간으로 변환 (존재하지 않는 시각이면 None)
+pub fn at_time(date: NaiveDate, time: NaiveTime) -> Option<DateTime<Local>> {
+    to_local(date.and_time(time)).ok()
+}
+
+// 특정 날짜의 하루 끝 시각을 로컬 시간으로 변환
+fn end_of_day(date: NaiveDate) -> Result<DateTime<Local>, String> {
+    to_local(date.and_time(end_of_day_time()))
+}
+
+// 시간대 정보가 없는 날짜/시각을 로컬 시간대로 해석
+// 서머타임 전환 등으로 모호한 경우 더 이른 시각을 선택
+fn to_local(naive: NaiveDateTime) -> Result<DateTime<Local>, String> {
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .ok_or_else(|| format!("로컬 시간으로 변환할 수 없는 시각입니다: {}", naive))
+}
@@ -0,0 +1,39 @@
+// 외부 편집기($EDITOR)로 여러 줄 텍스트를 편집하는 모듈
+use std::env;             // 환경 변수와 임시 디렉터리
+use std::fs;              // 임시 파일 읽기/쓰기
+use std::io;              // I/O 에러 타입
+use std::process::Command; // 외부 프로그램 실행
+
+// 편집기 환경 변수가 없을 때 사용할 기본 편집기
+const DEFAULT_EDITOR: &str = "vi";
+
+// 임시 파일에 initial 내용을 써 두고 편집기를 띄운 뒤, 저장된 내용을 돌려줌
+// name은 임시 파일 이름을 구분하는 데 사용 (예: 할일 ID)
+pub fn edit_text(name: &str, initial: &str) -> io::Result<String> {
+    // 프로세스 ID를 붙여 동시에 여러 편집기를 열어도 파일이 겹치지 않도록 함
+    let path = env::temp_dir().join(format!("todo-{}-{}.md", name, std::process::id()));
+    fs::write(&path, initial)?;
+
+    // 편집이 끝나면 성공/실패와 관계없이 임시 파일 삭제
+    let result = run_editor(&path).and_then(|_| fs::read_to_string(&path));
+    let _ = fs::remove_file(&path);
+    result
+}
+
+// $VISUAL → $EDITOR → vi 순서로 편집기를 찾아 실행하고 종료될 때까지 대기
+// "code --wait"처럼 인자가 붙은 값도 지원
+fn run_editor(path: &std::path::Path) -> io::Result<()> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| DEFAULT_EDITOR.to_string());
+
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or(DEFAULT_EDITOR);
+    let status = Command::new(program).args(parts).arg(path).status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("편집기가 비정상 종료되었습니다: {}", status)))
+    }
+}
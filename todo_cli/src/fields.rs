@@ -0,0 +1,152 @@
+// 사용자 정의 필드의 스키마와 값을 다루는 모듈
+use crate::dates;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+
+// 필드 정의 - 설정 파일의 "fields" 배열 항목 하나
+// 예: {"name": "sprint", "type": "enum", "values": ["s1", "s2"]}
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldDef {
+    pub name: String,                    // 필드 이름
+    #[serde(flatten)]
+    pub kind: FieldKind,                 // 값의 종류
+}
+
+// 필드 값의 종류
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FieldKind {
+    String,                              // 자유 문자열
+    Number,                              // 숫자
+    Date,                                // 날짜 (YYYY-MM-DD로 저장)
+    Enum { values: Vec<String> },        // 정해진 값 중 하나
+}
+
+// 할일에 저장되는 필드 값
+// untagged: JSON에는 숫자는 숫자로, 나머지는 문자열로 그대로 저장
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FieldValue {
+    Number(f64),
+    Text(String),
+}
+
+impl FieldDef {
+    // 입력 문자열을 스키마에 맞게 검증하여 값으로 변환
+    pub fn parse_value(&self, input: &str) -> Result<FieldValue, String> {
+        let input = input.trim();
+        match &self.kind {
+            FieldKind::String => Ok(FieldValue::Text(input.to_string())),
+            FieldKind::Number => input
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite())
+                .map(FieldValue::Number)
+                .ok_or_else(|| format!("'{}' 필드는 숫자여야 합니다: '{}'", self.name, input)),
+            FieldKind::Date => dates::parse_datetime(input)
+                .map(|d| FieldValue::Text(d.format("%Y-%m-%d").to_string())),
+            FieldKind::Enum { values } => {
+                if values.iter().any(|v| v == input) {
+                    Ok(FieldValue::Text(input.to_string()))
+                } else {
+                    Err(format!(
+                        "'{}' 필드는 다음 중 하나여야 합니다: {}",
+                        self.name,
+                        values.join(", ")
+                    ))
+                }
+            }
+        }
+    }
+
+    // 필드 목록 출력용 종류 설명
+    pub fn describe(&self) -> String {
+        match &self.kind {
+            FieldKind::String => "문자열".to_string(),
+            FieldKind::Number => "숫자".to_string(),
+            FieldKind::Date => "날짜".to_string(),
+            FieldKind::Enum { values } => format!("선택 ({})", values.join(" | ")),
+        }
+    }
+}
+
+impl FieldValue {
+    // 같은 종류의 값끼리 비교 (숫자는 크기, 문자열/날짜는 사전순)
+    // 종류가 다르면 숫자를 앞에 둠
+    pub fn compare(&self, other: &FieldValue) -> Ordering {
+        match (self, other) {
+            (FieldValue::Number(a), FieldValue::Number(b)) => a.total_cmp(b),
+            (FieldValue::Text(a), FieldValue::Text(b)) => a.cmp(b),
+            (FieldValue::Number(_), FieldValue::Text(_)) => Ordering::Less,
+            (FieldValue::Text(_), FieldValue::Number(_)) => Ordering::Greater,
+        }
+    }
+}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::Number(n) => write!(f, "{}", n),
+            FieldValue::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+// 목록 필터 조건 - "customer=acme", "ticket>=100", "sprint!=s1" 형태
+#[derive(Debug)]
+pub struct FieldCondition {
+    pub field: String,
+    op: Ordering,                        // 비교 결과가 이 값이어야 함
+    negate: bool,                        // true이면 결과를 뒤집음 (!=, <=, >=)
+    value: FieldValue,
+}
+
+impl FieldCondition {
+    // 조건 문자열을 스키마로 검증하여 파싱
+    pub fn parse(input: &str, schema: &[FieldDef]) -> Result<Self, String> {
+        // 두 글자 연산자를 먼저 확인해야 ">="가 ">"로 잘못 인식되지 않음
+        const OPERATORS: [(&str, Ordering, bool); 6] = [
+            ("!=", Ordering::Equal, true),
+            (">=", Ordering::Less, true),
+            ("<=", Ordering::Greater, true),
+            ("=", Ordering::Equal, false),
+            (">", Ordering::Greater, false),
+            ("<", Ordering::Less, false),
+        ];
+
+        let (field, op, negate, value) = OPERATORS
+            .iter()
+            .find_map(|(symbol, op, negate)| {
+                input
+                    .split_once(symbol)
+                    .map(|(field, value)| (field.trim(), *op, *negate, value))
+            })
+            .ok_or_else(|| format!("조건 형식을 알 수 없습니다: '{}' (예: customer=acme, ticket>=100)", input))?;
+
+        let def = find(schema, field)?;
+        Ok(Self {
+            field: def.name.clone(),
+            op,
+            negate,
+            value: def.parse_value(value)?,
+        })
+    }
+
+    // 할일의 필드 값이 조건을 만족하는지 확인
+    // 값이 없는 할일은 "!=" 조건만 만족
+    pub fn matches(&self, value: Option<&FieldValue>) -> bool {
+        match value {
+            Some(value) => (value.compare(&self.value) == self.op) != self.negate,
+            None => self.negate && self.op == Ordering::Equal,
+        }
+    }
+}
+
+// 스키마에서 이름으로 필드 정의를 찾음
+pub fn find<'a>(schema: &'a [FieldDef], name: &str) -> Result<&'a FieldDef, String> {
+    schema
+        .iter()
+        .find(|def| def.name == name)
+        .ok_or_else(|| format!("정의되지 않은 필드입니다: '{}' (todo fields로 확인)", name))
+}
@@ -1,12 +1,28 @@
 // 모듈 선언 - Rust의 모듈 시스템을 사용하여 코드를 구조화
 mod app;      // 애플리케이션 로직을 포함하는 모듈
+mod attachments; // 첨부 파일 저장소 모듈
+mod config;   // 사용자 설정 파일 모듈
+mod dates;    // 날짜/기간 문자열 파싱 모듈
+mod editor;   // 외부 편집기 실행 모듈
+mod fields;   // 사용자 정의 필드 모듈
+mod notify;   // 알림 전송 모듈
+mod recurrence; // 반복 규칙 모듈
+#[cfg(feature = "sqlite")]
+mod sqlite;   // SQLite 저장소 백엔드 모듈
 mod storage;  // 파일 저장/불러오기를 담당하는 모듈
 mod todo;     // Todo 데이터 구조를 정의하는 모듈
 
 // 외부 크레이트와 모듈 임포트
-use app::TodoApp;
-use clap::{Parser, Subcommand};  // clap: 커맨드라인 인자 파싱을 위한 크레이트
+use app::{AddOptions, EditOptions, ListFilter, Placement, TodoApp};
+use config::Config;              // 사용자 설정
+use storage::{Backend, JsonBackend}; // 저장소 백엔드
+use chrono::{DateTime, Duration, Local};   // 마감 기한 타입
+use clap::{ArgGroup, Parser, Subcommand, ValueEnum};  // clap: 커맨드라인 인자 파싱을 위한 크레이트
+use colored::*;                  // 터미널 컬러 출력
 use std::error::Error;           // 표준 에러 트레이트
+use std::process;                // 잘못된 ID로 종료
+use recurrence::Recurrence;      // 반복 규칙
+use todo::{Priority, Status};    // 우선순위, 진행 상태 열거형
 
 // CLI 구조체 정의 - clap의 derive 매크로를 사용하여 자동으로 파서 생성
 #[derive(Parser)]
@@ -28,23 +44,329 @@ enum Commands {
         /// 할 일 내용 - 공백을 포함할 수 있도록 Vec<String>으로 받음
         #[arg(required = true)]
         title: Vec<String>,
+        /// 우선순위 (high, medium, low, none)
+        #[arg(short, long, value_enum, default_value_t = Priority::None)]
+        priority: Priority,
+        /// 마감 기한 (예: today, tomorrow, +3d, 2025-06-20, "2025-06-20 14:30")
+        #[arg(short, long, value_parser = dates::parse_datetime)]
+        due: Option<DateTime<Local>>,
+        /// 태그 (여러 번 지정 가능)
+        #[arg(short, long = "tag")]
+        tags: Vec<String>,
+        /// 프로젝트 (점으로 구분, 예: work.backend.api)
+        #[arg(short = 'P', long)]
+        project: Option<String>,
+        /// 상위 할 일 번호 또는 UUID 앞부분 (하위 할 일로 추가)
+        #[arg(long)]
+        parent: Option<String>,
+        /// 먼저 끝나야 하는 할 일 번호 또는 UUID 앞부분 (쉼표로 구분, 예: 3,4)
+        #[arg(long, value_delimiter = ',')]
+        depends_on: Vec<String>,
+        /// 반복 규칙 (예: daily, weekly:mon,thu, monthly:15, after:3)
+        #[arg(short, long, value_parser = recurrence::parse)]
+        recur: Option<Recurrence>,
+        /// 사용자 정의 필드 값 (예: --field customer=acme, 여러 번 지정 가능)
+        #[arg(short, long = "field")]
+        fields: Vec<String>,
+        /// 시작일 - 이때까지 목록에서 숨김 (예: 3d, tomorrow, 2025-07-01)
+        #[arg(short, long, value_parser = dates::parse_start)]
+        wait: Option<DateTime<Local>>,
+    },
+    /// 할 일 목록 보기
+    List {
+        /// 이 태그가 붙은 할 일만 보기
+        #[arg(short, long)]
+        tag: Option<String>,
+        /// 이 프로젝트(하위 프로젝트 포함)의 할 일만 보기
+        #[arg(short = 'P', long)]
+        project: Option<String>,
+        /// 이 상태의 할 일만 보기
+        #[arg(short, long, value_enum)]
+        status: Option<Status>,
+        /// 사용자 정의 필드 조건 (예: customer=acme, ticket>=100, 여러 번 지정 가능)
+        #[arg(short = 'w', long = "where")]
+        conditions: Vec<String>,
+        /// 이 시각까지 마감인 할 일만 보기 (예: today, +3d, 2025-06-20)
+        #[arg(long, value_parser = dates::parse_datetime)]
+        due_before: Option<DateTime<Local>>,
+        /// 정렬 기준 (priority, due, created, id, title, manual 또는 필드 이름)
+        #[arg(long)]
+        sort: Option<String>,
+        /// 시작일이 아직 오지 않아 숨겨진 할 일도 보기
+        #[arg(short, long)]
+        all: bool,
     },
-    /// 모든 할 일 목록 보기
-    List,
     /// 할 일 완료/미완료 토글
     Toggle {
-        /// 할 일 ID
-        id: usize,
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 묻지 않고 미완료 하위 할 일도 함께 완료
+        #[arg(long)]
+        children: bool,
+    },
+    /// 진행 상태 변경 (todo, in-progress, blocked, done, cancelled)
+    Status {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 새 상태
+        #[arg(value_enum)]
+        state: Status,
+    },
+    /// 할 일 제목/우선순위/프로젝트 수정
+    Edit {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 새 제목
+        #[arg(short, long)]
+        title: Option<String>,
+        /// 새 우선순위
+        #[arg(short, long, value_enum)]
+        priority: Option<Priority>,
+        /// 새 프로젝트 (빈 문자열이면 프로젝트 제거)
+        #[arg(short = 'P', long)]
+        project: Option<String>,
+        /// 새 상위 할 일 번호 또는 UUID 앞부분 (0이면 최상위로 이동)
+        #[arg(long)]
+        parent: Option<String>,
+    },
+    /// 마감 기한 설정 (날짜를 생략하면 기한 제거)
+    Due {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 마감 기한 (예: today, tomorrow, +3d, 2025-06-20, "2025-06-20 14:30")
+        #[arg(value_parser = dates::parse_datetime)]
+        date: Option<DateTime<Local>>,
+    },
+    /// 태그 추가/제거 (예: todo tag 3 +work -home)
+    Tag {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// +태그는 추가, -태그는 제거
+        #[arg(required = true, allow_hyphen_values = true)]
+        changes: Vec<String>,
+    },
+    /// 모든 태그와 태그별 할 일 수 보기
+    Tags,
+    /// 프로젝트 트리와 프로젝트별 완료율 보기
+    Projects,
+    /// 선행 할 일 지정 (예: todo depend 5 3 4 → 3, 4가 끝나야 5 진행 가능)
+    Depend {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 먼저 끝나야 하는 할 일 번호 또는 UUID 앞부분들
+        #[arg(required = true)]
+        on: Vec<String>,
+        /// 추가하는 대신 의존 관계 제거
+        #[arg(long)]
+        remove: bool,
+    },
+    /// 지금 바로 진행할 수 있는 할 일 보기
+    Next,
+    /// 반복 규칙 설정 (규칙을 생략하면 반복 해제)
+    Recur {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 반복 규칙 (예: daily, weekly:mon,thu, monthly:15, after:3)
+        #[arg(value_parser = recurrence::parse)]
+        rule: Option<Recurrence>,
+    },
+    /// 할 일의 모든 정보 보기 (메모 포함)
+    Show {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+    },
+    /// 메모 편집 ($EDITOR 사용)
+    Note {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 편집기를 열지 않고 이 내용으로 메모 저장
+        #[arg(short, long)]
+        message: Option<String>,
+    },
+    /// 작업 시간 기록 시작
+    Start {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+    },
+    /// 실행 중인 타이머 정지
+    Stop,
+    /// 사용자 정의 필드 값 설정 (예: todo set 3 customer=acme ticket=120, 값을 비우면 제거)
+    Set {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 이름=값
+        #[arg(required = true)]
+        assignments: Vec<String>,
+    },
+    /// 설정 파일에 정의된 사용자 정의 필드 보기
+    Fields,
+    /// 시작일을 미뤄서 그때까지 목록에서 숨김 (시점을 생략하면 다시 표시)
+    Snooze {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 언제까지 숨길지 (예: 3d, 2w, tomorrow, 2025-07-01)
+        #[arg(value_parser = dates::parse_start)]
+        until: Option<DateTime<Local>>,
     },
-    /// 할 일 삭제
+    /// 알림 추가/보기 (예: todo remind 3 30m, todo remind --watch)
+    Remind {
+        /// 할 일 번호 또는 UUID 앞부분 (생략하면 예정된 알림 목록 보기)
+        id: Option<String>,
+        /// 알림 시각 (예: 30m, 2h, tomorrow, "2025-06-20 09:00")
+        #[arg(value_parser = dates::parse_start)]
+        at: Option<DateTime<Local>>,
+        /// 해당 할 일의 알림을 모두 제거
+        #[arg(long, requires = "id")]
+        clear: bool,
+        /// 알림 시각마다 알려주는 감시 모드로 계속 실행
+        #[arg(long, conflicts_with_all = ["id", "at", "clear"])]
+        watch: bool,
+    },
+    /// 체크리스트 관리 (예: todo check add 3 "릴리스 노트 작성")
+    Check {
+        #[command(subcommand)]
+        action: CheckAction,
+    },
+    /// 파일이나 링크 첨부 (예: todo attach 3 ./spec.pdf, todo attach 3 https://...)
+    Attach {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 파일 경로 또는 URL
+        target: String,
+    },
+    /// 첨부 제거
+    Detach {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 첨부 번호 (1부터, todo open으로 확인)
+        n: usize,
+    },
+    /// 첨부 목록 보기/열기
+    Open {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 열 첨부 번호 (생략하면 목록 보기, 하나뿐이면 바로 열기)
+        n: Option<usize>,
+    },
+    /// 할 일 순서 변경 (예: todo move 5 --before 2)
+    #[command(group(ArgGroup::new("place").required(true).args(["before", "top", "bottom"])))]
+    Move {
+        /// 옮길 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 이 할 일 바로 앞으로 (번호 또는 UUID 앞부분)
+        #[arg(long)]
+        before: Option<String>,
+        /// 맨 위로
+        #[arg(long)]
+        top: bool,
+        /// 맨 아래로
+        #[arg(long)]
+        bottom: bool,
+    },
+    /// 할 일 삭제 (휴지통으로 이동, todo restore로 복원)
     Delete {
-        /// 할 일 ID
-        id: usize,
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 하위 할 일도 모두 삭제 (기본: 하위 할 일을 한 단계 위로 이동)
+        #[arg(long)]
+        cascade: bool,
+    },
+    /// 휴지통 보기/비우기 (기본: 목록)
+    Trash {
+        #[command(subcommand)]
+        action: Option<TrashAction>,
+    },
+    /// 휴지통의 할 일을 원래 ID로 복원
+    Restore {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
     },
-    /// 완료된 모든 할 일 삭제
+    /// 완료/취소된 모든 할 일을 보관함으로 옮기기
     Clear,
+    /// 보관함 보기/검색/복원 (기본: 목록)
+    Archive {
+        #[command(subcommand)]
+        action: Option<ArchiveAction>,
+    },
     /// 통계 보기
     Stats,
+    /// 저장소 형식 바꾸기 (todos.json 등을 SQLite 데이터베이스로 복사)
+    Migrate {
+        /// 옮겨 갈 저장소 형식
+        #[arg(long, value_enum)]
+        to: StorageKind,
+    },
+}
+
+// migrate 명령으로 옮겨 갈 수 있는 저장소 형식
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum StorageKind {
+    Sqlite,               // todos.db (cargo build --features sqlite 필요)
+}
+
+// archive 명령의 하위 명령
+#[derive(Subcommand)]
+enum ArchiveAction {
+    /// 보관된 할 일 목록
+    List,
+    /// 보관된 할 일 검색 (제목, 메모, 태그, 프로젝트)
+    Search {
+        /// 검색어
+        query: String,
+    },
+    /// 보관된 할 일을 다시 목록으로 복원
+    Restore {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+    },
+}
+
+// trash 명령의 하위 명령
+#[derive(Subcommand)]
+enum TrashAction {
+    /// 휴지통의 할 일 목록
+    List,
+    /// 휴지통 비우기 (영구 삭제)
+    Purge {
+        /// 이 기간보다 오래 전에 삭제된 할 일만 비우기 (예: 30d, 2w)
+        #[arg(long, value_parser = dates::parse_duration)]
+        older_than: Option<Duration>,
+    },
+}
+
+// check 명령의 하위 명령
+#[derive(Subcommand)]
+enum CheckAction {
+    /// 체크리스트 항목 추가
+    Add {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 항목 내용
+        text: String,
+    },
+    /// 체크리스트 항목 체크/해제
+    Toggle {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 항목 번호 (1부터)
+        n: usize,
+    },
+    /// 체크리스트 항목 삭제
+    Remove {
+        /// 할 일 번호 또는 UUID 앞부분
+        id: String,
+        /// 항목 번호 (1부터)
+        n: usize,
+    },
+}
+
+// 번호 또는 UUID 앞부분을 찾은 결과에서 할 일 번호를 꺼냄
+// 일치하는 할 일이 없거나 여러 개이면 메시지를 출력하고 종료
+fn resolved(result: Result<usize, String>) -> usize {
+    result.unwrap_or_else(|e| {
+        println!("{}", format!("❌ {}", e).red());
+        process::exit(1);
+    })
 }
 
 // 메인 함수 - Result를 반환하여 에러 처리를 간편하게 함
@@ -52,46 +374,276 @@ fn main() -> Result<(), Box<dyn Error>> {
     // 커맨드라인 인자를 파싱하여 Cli 구조체 생성
     let cli = Cli::parse();
     
-    // TodoApp 인스턴스 생성 - ?를 사용하여 에러 전파
-    let mut app = TodoApp::new()?;
+    // 저장소를 옮기는 명령은 TodoApp 없이 처리
+    if let Commands::Migrate { to } = cli.command {
+        return migrate(to);
+    }
+
+    // 저장소 백엔드 선택 (todos.db가 있으면 SQLite, 없으면 JSON 파일)
+    let backend = open_backend()?;
+
+    // TodoApp 인스턴스 생성 - 다른 todo 명령이 저장소를 잠그고 있으면 기다렸다가 안내 후 종료
+    let mut app = TodoApp::new(backend, Config::load()?).unwrap_or_else(|e| {
+        println!("{}", format!("❌ {}", e).red());
+        process::exit(1);
+    });
 
     // 패턴 매칭을 사용하여 각 명령어에 따른 처리
     match cli.command {
-        Commands::Add { title } => {
+        Commands::Add { title, priority, due, tags, project, parent, depends_on, recur, fields, wait } => {
             // Vec<String>을 하나의 문자열로 합침 (공백 포함 제목 지원)
             let title = title.join(" ");
-            app.add(title)?;
+            let parent = parent.map(|p| resolved(app.resolve(&p)));
+            let depends_on = depends_on.iter().map(|d| resolved(app.resolve(d))).collect();
+            let options = AddOptions {
+                priority,
+                due,
+                tags,
+                project,
+                parent,
+                depends_on,
+                recurrence: recur,
+                fields,
+                wait,
+            };
+            app.add(title, options)?;
         }
-        Commands::List => {
-            // 할일 목록 출력 (에러가 발생하지 않는 작업)
-            app.list();
+        Commands::List { tag, project, status, conditions, due_before, sort, all } => {
+            // 할일 목록 출력
+            app.list(&ListFilter { tag, project, status, conditions, due_before, all }, sort.as_deref())?;
         }
-        Commands::Toggle { id } => {
+        Commands::Toggle { id, children } => {
+            let id = resolved(app.resolve(&id));
             // 특정 ID의 할일 상태 토글
-            app.toggle(id)?;
+            app.toggle(id, children)?;
+        }
+        Commands::Status { id, state } => {
+            let id = resolved(app.resolve(&id));
+            // 진행 상태 변경
+            app.set_status(id, state)?;
+        }
+        Commands::Edit { id, title, priority, project, parent } => {
+            let id = resolved(app.resolve(&id));
+            // 지정된 항목만 수정
+            let parent = parent.map(|p| resolved(app.resolve(&p)));
+            app.edit(id, EditOptions { title, priority, project, parent })?;
+        }
+        Commands::Due { id, date } => {
+            let id = resolved(app.resolve(&id));
+            // 마감 기한 설정 또는 제거
+            app.set_due(id, date)?;
+        }
+        Commands::Tag { id, changes } => {
+            let id = resolved(app.resolve(&id));
+            // 태그 추가/제거
+            app.tag(id, &changes)?;
+        }
+        Commands::Tags => {
+            // 태그별 집계 출력
+            app.tags();
+        }
+        Commands::Projects => {
+            // 프로젝트 트리 출력
+            app.projects();
+        }
+        Commands::Depend { id, on, remove } => {
+            let id = resolved(app.resolve(&id));
+            // 의존 관계 추가/제거
+            let on: Vec<usize> = on.iter().map(|d| resolved(app.resolve(d))).collect();
+            app.depend(id, &on, remove)?;
+        }
+        Commands::Next => {
+            // 진행 가능한 할일 출력
+            app.next();
+        }
+        Commands::Recur { id, rule } => {
+            let id = resolved(app.resolve(&id));
+            // 반복 규칙 설정 또는 해제
+            app.set_recurrence(id, rule)?;
         }
-        Commands::Delete { id } => {
+        Commands::Show { id } => {
+            let id = resolved(app.resolve(&id));
+            // 할일 상세 정보 출력
+            app.show(id);
+        }
+        Commands::Note { id, message } => {
+            let id = resolved(app.resolve(&id));
+            // 메모 편집
+            app.note(id, message)?;
+        }
+        Commands::Start { id } => {
+            let id = resolved(app.resolve(&id));
+            // 타이머 시작
+            app.start(id)?;
+        }
+        Commands::Stop => {
+            // 타이머 정지
+            app.stop()?;
+        }
+        Commands::Set { id, assignments } => {
+            let id = resolved(app.resolve(&id));
+            // 사용자 정의 필드 값 설정
+            app.set_fields(id, &assignments)?;
+        }
+        Commands::Fields => {
+            // 필드 스키마 출력
+            app.fields();
+        }
+        Commands::Snooze { id, until } => {
+            let id = resolved(app.resolve(&id));
+            // 시작일 미루기
+            app.snooze(id, until)?;
+        }
+        Commands::Remind { id, at, clear, watch } => {
+            // 감시 모드, 알림 추가/제거, 알림 목록 중 하나
+            match id {
+                _ if watch => app.watch_reminders()?,
+                Some(id) => app.remind(resolved(app.resolve(&id)), at, clear)?,
+                None => app.reminders(),
+            }
+        }
+        Commands::Check { action } => {
+            // 체크리스트 항목 추가/체크/삭제
+            match action {
+                CheckAction::Add { id, text } => app.check_add(resolved(app.resolve(&id)), &text)?,
+                CheckAction::Toggle { id, n } => app.check_toggle(resolved(app.resolve(&id)), n)?,
+                CheckAction::Remove { id, n } => app.check_remove(resolved(app.resolve(&id)), n)?,
+            }
+        }
+        Commands::Attach { id, target } => {
+            let id = resolved(app.resolve(&id));
+            // 파일은 첨부 저장소로 복사, URL은 링크로 저장
+            app.attach(id, &target)?;
+        }
+        Commands::Detach { id, n } => {
+            let id = resolved(app.resolve(&id));
+            app.detach(id, n)?;
+        }
+        Commands::Open { id, n } => {
+            let id = resolved(app.resolve(&id));
+            app.open(id, n)?;
+        }
+        Commands::Move { id, before, top, .. } => {
+            let id = resolved(app.resolve(&id));
+            // ArgGroup으로 셋 중 정확히 하나만 지정됨
+            let placement = match before {
+                Some(target) => Placement::Before(resolved(app.resolve(&target))),
+                None if top => Placement::Top,
+                None => Placement::Bottom,
+            };
+            app.move_todo(id, placement)?;
+        }
+        Commands::Delete { id, cascade } => {
+            let id = resolved(app.resolve(&id));
             // 특정 ID의 할일 삭제
-            app.delete(id)?;
+            app.delete(id, cascade)?;
+        }
+        Commands::Trash { action } => {
+            match action.unwrap_or(TrashAction::List) {
+                TrashAction::List => app.trash_list()?,
+                TrashAction::Purge { older_than } => app.purge_trash(older_than)?,
+            }
+        }
+        Commands::Restore { id } => {
+            let id = resolved(app.resolve_trashed(&id));
+            // 휴지통에서 원래 ID로 복원
+            app.restore(id)?;
         }
         Commands::Clear => {
-            // 완료된 할일 모두 삭제
+            // 완료/취소된 할일을 모두 보관함으로 옮김
             app.clear_completed()?;
         }
+        Commands::Archive { action } => {
+            match action.unwrap_or(ArchiveAction::List) {
+                ArchiveAction::List => app.archive_list()?,
+                ArchiveAction::Search { query } => app.archive_search(&query)?,
+                ArchiveAction::Restore { id } => app.archive_restore(resolved(app.resolve_archived(&id)))?,
+            }
+        }
         Commands::Stats => {
             // 통계 정보 출력 (에러가 발생하지 않는 작업)
             app.stats();
         }
+        Commands::Migrate { .. } => {
+            // 위에서 TodoApp을 만들기 전에 처리됨
+        }
     }
 
     // 정상 종료
     Ok(())
 }
 
+// 사용할 저장소 백엔드 - todos.db가 있으면 SQLite, 없으면 JSON 파일
+fn open_backend() -> Result<Box<dyn Backend>, Box<dyn Error>> {
+    #[cfg(feature = "sqlite")]
+    if sqlite::SqliteBackend::exists() {
+        return Ok(Box::new(sqlite::SqliteBackend::open()?));
+    }
+    Ok(Box::new(JsonBackend::new()))
+}
+
+// migrate 명령 - JSON 파일의 모든 묶음(목록, 보관함, 휴지통)을 SQLite로 복사
+// 임시 파일에 옮긴 뒤 원본과 같은지 확인이 끝나야 todos.db로 이름을 바꾸고, JSON 파일은 백업으로 남겨 둠
+#[cfg(feature = "sqlite")]
+fn migrate(to: StorageKind) -> Result<(), Box<dyn Error>> {
+    let StorageKind::Sqlite = to;
+    if sqlite::SqliteBackend::exists() {
+        println!("{}", format!("❌ 이미 {} 파일이 있습니다.", sqlite::DATABASE_FILE).red());
+        return Ok(());
+    }
+
+    let source = JsonBackend::new();
+    let _lock = source.lock(app::LOCK_TIMEOUT).unwrap_or_else(|e| {
+        println!("{}", format!("❌ {}", e).red());
+        process::exit(1);
+    });
+
+    // 실패하면 만들던 임시 파일을 지워서 반쯤 옮긴 데이터베이스가 남지 않게 함
+    let temp = std::path::PathBuf::from(format!("{}.tmp", sqlite::DATABASE_FILE));
+    let _ = std::fs::remove_file(&temp);  // 이전에 중단된 migrate가 남긴 파일
+    let copied = match copy_to_sqlite(&source, &temp) {
+        Ok(copied) => copied,
+        Err(e) => {
+            let _ = std::fs::remove_file(&temp);
+            println!("{}", format!("❌ {} {}를 만들지 않았습니다.", e, sqlite::DATABASE_FILE).red());
+            process::exit(1);
+        }
+    };
+    std::fs::rename(&temp, sqlite::DATABASE_FILE)?;
+
+    println!("{}", format!("✅ 할 일 {}개를 {}로 옮겼습니다.", copied, sqlite::DATABASE_FILE).green());
+    println!("{}", "💾 JSON 파일은 백업으로 남겨 두었습니다. 이제부터는 데이터베이스를 사용합니다.".dimmed());
+    Ok(())
+}
+
+// path의 새 데이터베이스에 모든 묶음을 복사하고, 묶음마다 할일 내용과 다음 ID가 그대로 옮겨졌는지 확인
+#[cfg(feature = "sqlite")]
+fn copy_to_sqlite(source: &JsonBackend, path: &std::path::Path) -> Result<usize, Box<dyn Error>> {
+    let target = sqlite::SqliteBackend::open_at(path)?;
+    let copied = storage::copy_all(source, &target).map_err(|e| format!("복사하지 못했습니다 - {}.", e))?;
+    for collection in storage::Collection::ALL {
+        let expected = serde_json::to_value(source.load(collection)?)?;
+        let actual = serde_json::to_value(target.load(collection)?)?;
+        if expected != actual {
+            return Err(format!("{} 묶음을 옮긴 내용이 원본과 다릅니다.", collection.name()).into());
+        }
+    }
+    Ok(copied)
+}
+
+// SQLite 지원 없이 빌드된 경우
+#[cfg(not(feature = "sqlite"))]
+fn migrate(to: StorageKind) -> Result<(), Box<dyn Error>> {
+    let StorageKind::Sqlite = to;
+    println!("{}", "❌ SQLite 지원 없이 빌드되었습니다. cargo build --features sqlite 로 다시 빌드하세요.".red());
+    process::exit(1);
+}
+
 // 테스트 모듈 - cfg(test) 속성으로 테스트 실행시에만 컴파일됨
 #[cfg(test)]
 mod tests {
     use super::*;
+    use storage::MemoryBackend;
 
     // Todo 생성 테스트
     #[test]
@@ -102,7 +654,7 @@ mod tests {
         // 생성된 Todo의 필드값 검증
         assert_eq!(todo.id, 1);
         assert_eq!(todo.title, "테스트 할 일");
-        assert!(!todo.completed);  // 새로 생성된 할일은 미완료 상태여야 함
+        assert_eq!(todo.status, Status::Todo);  // 새로 생성된 할일은 "할 일" 상태여야 함
     }
 
     // Todo 토글 기능 테스트
@@ -112,14 +664,606 @@ mod tests {
         let mut todo = todo::Todo::new(1, "테스트".to_string());
         
         // 초기 상태는 미완료
-        assert!(!todo.completed);
+        assert!(!todo.is_closed());
         
         // 토글 후 완료 상태로 변경 확인
         todo.toggle();
-        assert!(todo.completed);
+        assert_eq!(todo.status, Status::Done);
         
         // 다시 토글하면 미완료 상태로 변경 확인
         todo.toggle();
-        assert!(!todo.completed);
+        assert_eq!(todo.status, Status::Todo);
+
+        // 취소된 할일을 토글하면 다시 "할 일"로
+        todo.set_status(Status::Cancelled);
+        todo.toggle();
+        assert_eq!(todo.status, Status::Todo);
+    }
+
+    // priority 필드가 없는 예전 JSON도 불러올 수 있는지 테스트
+    #[test]
+    fn test_priority_default_for_old_json() {
+        let json = r#"[{
+            "id": 1,
+            "title": "예전 할 일",
+            "completed": false,
+            "created_at": "2025-06-16T15:10:34.291386+09:00",
+            "updated_at": "2025-06-16T15:10:34.291386+09:00"
+        }]"#;
+        // 불러올 때처럼 예전 형식을 현재 버전으로 올린 뒤 읽음
+        let upgraded = storage::upgrade(serde_json::from_str(json).unwrap()).unwrap();
+        let todos: Vec<todo::Todo> = serde_json::from_value(upgraded.todos).unwrap();
+        assert_eq!(todos[0].priority, Priority::None);
+
+        // 정렬 순서: 높음 → 보통 → 낮음 → 없음
+        assert!(Priority::High < Priority::Medium);
+        assert!(Priority::Low < Priority::None);
+    }
+
+    // 예전 completed 불린 값이 status로 변환되는지 테스트
+    #[test]
+    fn test_migrate_completed_flag() {
+        let mut value = serde_json::json!([
+            { "id": 1, "title": "끝난 일", "completed": true,
+              "created_at": "2025-06-16T15:10:34+09:00", "updated_at": "2025-06-16T15:10:34+09:00" },
+            { "id": 2, "title": "남은 일", "completed": false,
+              "created_at": "2025-06-16T15:10:34+09:00", "updated_at": "2025-06-16T15:10:34+09:00" },
+        ]);
+        storage::migrate_completed_flag(&mut value);
+        storage::assign_missing_uuids(&mut value);  // 같은 단계(0 → 1)에서 UUID도 부여됨
+
+        let todos: Vec<todo::Todo> = serde_json::from_value(value).unwrap();
+        assert_eq!(todos[0].status, Status::Done);
+        assert_eq!(todos[1].status, Status::Todo);
+
+        // 저장할 때는 completed 필드 없이 status만 기록
+        let json = serde_json::to_string(&todos[0]).unwrap();
+        assert!(json.contains(r#""status":"done""#));
+        assert!(!json.contains("completed"));
+    }
+
+    // 사용자 정의 필드 스키마 검증과 필터 조건 테스트
+    #[test]
+    fn test_custom_fields() {
+        let schema: Vec<fields::FieldDef> = serde_json::from_str(
+            r#"[
+                {"name": "ticket", "type": "number"},
+                {"name": "sprint", "type": "enum", "values": ["s1", "s2"]},
+                {"name": "review", "type": "date"}
+            ]"#,
+        )
+        .unwrap();
+
+        // 값 검증
+        assert!(schema[0].parse_value("12a").is_err());
+        assert!(schema[1].parse_value("s3").is_err());
+        assert_eq!(schema[1].parse_value("s2"), Ok(fields::FieldValue::Text("s2".to_string())));
+        assert_eq!(
+            schema[2].parse_value("2025-06-20"),
+            Ok(fields::FieldValue::Text("2025-06-20".to_string()))
+        );
+
+        // 숫자는 문자열이 아닌 크기로 비교 (100 > 99)
+        let cond = fields::FieldCondition::parse("ticket>=99", &schema).unwrap();
+        assert!(cond.matches(Some(&fields::FieldValue::Number(100.0))));
+        assert!(!cond.matches(Some(&fields::FieldValue::Number(98.0))));
+        assert!(!cond.matches(None));
+
+        // 값이 없는 할일은 != 조건만 만족
+        let cond = fields::FieldCondition::parse("sprint!=s1", &schema).unwrap();
+        assert!(cond.matches(None));
+        assert!(!cond.matches(Some(&fields::FieldValue::Text("s1".to_string()))));
+
+        // 정의되지 않은 필드는 거부
+        assert!(fields::FieldCondition::parse("customer=acme", &schema).is_err());
+    }
+
+    // 마감 기한 파싱과 기한 지남/오늘 마감 판정 테스트
+    #[test]
+    fn test_due_dates() {
+        let now = Local::now();
+        let mut todo = todo::Todo::new(1, "마감 테스트".to_string());
+        assert!(!todo.is_overdue(now));
+
+        // 어제 마감 → 기한 지남
+        todo.set_due(Some(now - chrono::Duration::days(1)));
+        assert!(todo.is_overdue(now));
+        assert!(!todo.is_due_today(now));
+
+        // 완료된 할일은 기한이 지나도 표시하지 않음
+        todo.toggle();
+        assert!(!todo.is_overdue(now));
+
+        // 날짜 문자열 파싱
+        let due = dates::parse_datetime("2025-06-20").unwrap();
+        assert_eq!(due.format("%Y-%m-%d %H:%M").to_string(), "2025-06-20 23:59");
+        assert!(dates::parse_datetime("+3d").unwrap() > now);
+        assert!(dates::parse_datetime("다음주쯤").is_err());
+        assert!(dates::parse_datetime("+3일").is_err());
+        assert!(dates::parse_duration("일").is_err());
     }
-}
\ No newline at end of file
+
+    // 시작일 전에는 숨겨지고 시작일이 지나면 다시 보이는지 테스트
+    #[test]
+    fn test_wait_hides_until_start() {
+        let now = Local::now();
+        let mut todo = todo::Todo::new(1, "다음 달에 할 일".to_string());
+        assert!(!todo.is_waiting(now));
+
+        todo.set_wait(Some(dates::parse_start("3d").unwrap()));
+        assert!(todo.is_waiting(now));
+        assert!(!todo.is_waiting(now + chrono::Duration::days(4)));
+
+        // 날짜만 쓰면 그날 0시부터 표시
+        let start = dates::parse_start("2025-07-01").unwrap();
+        assert_eq!(start.format("%Y-%m-%d %H:%M").to_string(), "2025-07-01 00:00");
+
+        // 내일은 내일 0시부터 표시
+        let tomorrow = dates::parse_start("내일").unwrap();
+        assert_eq!(tomorrow.date_naive(), now.date_naive() + chrono::Duration::days(1));
+        assert_eq!(tomorrow.format("%H:%M").to_string(), "00:00");
+    }
+
+    // 프로젝트 경로 정리와 하위 프로젝트 포함 여부 테스트
+    #[test]
+    fn test_project_hierarchy() {
+        let mut todo = todo::Todo::new(1, "API 작성".to_string());
+        todo.set_project(Some(" work..backend.api. "));
+        assert_eq!(todo.project.as_deref(), Some("work.backend.api"));
+
+        assert!(todo.in_project("work"));
+        assert!(todo.in_project("work.backend"));
+        assert!(todo.in_project("work.backend.api"));
+        assert!(!todo.in_project("wor"));
+        assert!(!todo.in_project("work.back"));
+
+        // 빈 문자열은 프로젝트 제거
+        todo.set_project(Some(""));
+        assert!(todo.project.is_none());
+    }
+
+    // 상위 할일이 삭제되면 하위 할일이 살아남은 조상에 다시 연결되는지 테스트
+    #[test]
+    fn test_reattach_orphans() {
+        // 1 ← 2 ← 3 구조에서 2를 삭제
+        let mut child = todo::Todo::new(3, "손자".to_string());
+        child.parent = Some(2);
+        let mut todos = vec![todo::Todo::new(1, "조상".to_string()), child];
+
+        let removed = std::collections::HashMap::from([(2, Some(1))]);
+        app::reattach_orphans(&mut todos, &removed);
+        assert_eq!(todos[1].parent, Some(1));
+
+        // 1과 2가 모두 삭제되면 최상위로
+        todos[1].parent = Some(2);
+        let removed = std::collections::HashMap::from([(2, Some(1)), (1, None)]);
+        app::reattach_orphans(&mut todos[1..], &removed);
+        assert_eq!(todos[1].parent, None);
+    }
+
+    // 의존 관계 순환 감지 테스트
+    #[test]
+    fn test_dependency_cycle() {
+        // 2는 1에, 3은 2에 의존
+        let mut todos: Vec<todo::Todo> = (1..=3)
+            .map(|id| todo::Todo::new(id, format!("할 일 {}", id)))
+            .collect();
+        todos[1].depends_on.insert(1);
+        todos[2].depends_on.insert(2);
+
+        // 1이 3에 의존하면 1 → 3 → 2 → 1 순환
+        assert!(app::creates_cycle(&todos, 1, 3));
+        // 자기 자신에 대한 의존도 순환
+        assert!(app::creates_cycle(&todos, 2, 2));
+        // 3이 1에 직접 의존하는 것은 문제없음
+        assert!(!app::creates_cycle(&todos, 3, 1));
+    }
+
+    // 반복 규칙 파싱과 다음 마감일 계산 테스트
+    #[test]
+    fn test_recurrence_next_due() {
+        use chrono::{Datelike, NaiveDate, TimeZone, Weekday};
+        let at = |y, m, d, h| Local.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap();
+
+        // 2025-06-16은 월요일, 마감 18시
+        let due = Some(at(2025, 6, 16, 18));
+
+        // 매일: 일찍 끝내도 다음 날 같은 시각
+        let daily = recurrence::parse("daily").unwrap();
+        assert_eq!(daily.next_due(due, at(2025, 6, 16, 9)), Some(at(2025, 6, 17, 18)));
+
+        // 매주 월, 목: 월요일 다음은 목요일
+        let weekly = recurrence::parse("weekly:mon,thu").unwrap();
+        let next = weekly.next_due(due, at(2025, 6, 16, 9)).unwrap();
+        assert_eq!(next.weekday(), Weekday::Thu);
+        assert_eq!(next, at(2025, 6, 19, 18));
+
+        // 매월 31일: 6월에는 31일이 없으므로 말일
+        let monthly = recurrence::parse("monthly:31").unwrap();
+        assert_eq!(monthly.next_due(due, at(2025, 6, 16, 9)), Some(at(2025, 6, 30, 18)));
+
+        // 완료 후 3일: 마감일이 아니라 완료한 날 기준
+        let after = recurrence::parse("after:3").unwrap();
+        assert_eq!(after.next_due(due, at(2025, 6, 20, 9)), Some(at(2025, 6, 23, 18)));
+
+        // 마감일이 없으면 완료한 날 다음 회차 (같은 날 다시 마감되지 않음)
+        let end_of = |y, m, d| dates::at_time(NaiveDate::from_ymd_opt(y, m, d).unwrap(), dates::end_of_day_time());
+        assert_eq!(daily.next_due(None, at(2025, 6, 16, 9)), end_of(2025, 6, 17));
+        assert_eq!(recurrence::parse("weekly").unwrap().next_due(None, at(2025, 6, 16, 9)), end_of(2025, 6, 23));
+        assert_eq!(recurrence::parse("monthly:16").unwrap().next_due(None, at(2025, 6, 16, 9)), end_of(2025, 7, 16));
+
+        assert!(recurrence::parse("yearly").is_err());
+        assert!(recurrence::parse("monthly:32").is_err());
+    }
+
+    // 반복 할일을 완료하면 다음 회차가 새 ID로 생성되는지 테스트
+    #[test]
+    fn test_next_occurrence() {
+        let mut todo = todo::Todo::new(1, "분리수거".to_string());
+        todo.recurrence = Some(recurrence::Recurrence::Daily);
+
+        // 완료되지 않은 할일은 다음 회차를 만들지 않음
+        assert!(todo.next_occurrence(2, Local::now()).is_none());
+
+        todo.toggle();
+        let next = todo.next_occurrence(2, Local::now()).unwrap();
+        assert_eq!(next.id, 2);
+        assert_eq!(next.status, Status::Todo);
+        assert!(next.due.is_some());
+        assert!(next.recurrence.is_some());
+
+        // 반복 규칙은 새 할일로 옮겨졌으므로 다시 생성되지 않음
+        assert!(todo.recurrence.is_none());
+        assert!(todo.next_occurrence(3, Local::now()).is_none());
+    }
+
+    // 타이머 시작/정지와 누적 시간 계산 테스트
+    #[test]
+    fn test_time_tracking() {
+        let start = Local::now();
+        let mut todo = todo::Todo::new(1, "버그 수정".to_string());
+
+        assert!(todo.start_timer(start));
+        // 이미 돌고 있는 타이머는 다시 시작할 수 없음
+        assert!(!todo.start_timer(start));
+        assert!(todo.is_tracking());
+
+        let elapsed = todo.stop_timer(start + chrono::Duration::minutes(30));
+        assert_eq!(elapsed, Some(chrono::Duration::minutes(30)));
+        assert!(todo.stop_timer(start).is_none());
+
+        // 두 번째 구간을 더하면 누적 시간이 합쳐짐
+        todo.start_timer(start + chrono::Duration::hours(1));
+        todo.stop_timer(start + chrono::Duration::hours(2));
+        assert_eq!(todo.tracked_time(start), chrono::Duration::minutes(90));
+        assert_eq!(dates::format_duration(todo.tracked_time(start)), "1시간 30분");
+    }
+
+    // 알림 추가와 반복 할일의 다음 회차로 알림이 옮겨지는지 테스트
+    #[test]
+    fn test_reminders() {
+        let now = Local::now();
+        let mut todo = todo::Todo::new(1, "보고서".to_string());
+        todo.set_due(Some(now + chrono::Duration::days(1)));
+        todo.recurrence = Some(recurrence::Recurrence::Daily);
+
+        assert!(todo.add_reminder(now + chrono::Duration::hours(20)));
+        // 같은 시각의 알림은 중복 추가되지 않음
+        assert!(!todo.add_reminder(now + chrono::Duration::hours(20)));
+        assert!(todo.has_pending_reminder());
+
+        todo.reminders[0].fired = true;
+        assert!(!todo.has_pending_reminder());
+
+        // 다음 회차는 마감일이 밀린 만큼 알림도 밀리고 다시 울릴 수 있는 상태가 됨
+        todo.toggle();
+        let next = todo.next_occurrence(2, now).unwrap();
+        let shift = next.due.unwrap() - todo.due.unwrap();
+        assert_eq!(next.reminders[0].at, todo.reminders[0].at + shift);
+        assert!(next.has_pending_reminder());
+    }
+
+    // 저장된 순서대로 정렬되고, 순서가 없던 예전 데이터는 파일 순서를 유지하는지 테스트
+    #[test]
+    fn test_normalize_positions() {
+        let mut todos: Vec<todo::Todo> = (1..=3).map(|id| todo::Todo::new(id, format!("할일 {}", id))).collect();
+        app::normalize_positions(&mut todos);
+        let ids: Vec<usize> = todos.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+
+        // ID와 상관없이 position 순서를 따름
+        todos[0].position = 10;
+        app::normalize_positions(&mut todos);
+        let ids: Vec<usize> = todos.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![2, 3, 1]);
+        let positions: Vec<usize> = todos.iter().map(|t| t.position).collect();
+        assert_eq!(positions, vec![1, 2, 3]);
+    }
+
+    // 체크리스트 항목 추가/체크/삭제와 반복 시 초기화 테스트
+    #[test]
+    fn test_checklist() {
+        let mut todo = todo::Todo::new(1, "릴리스".to_string());
+        assert_eq!(todo.add_check_item("태그 달기"), 1);
+        assert_eq!(todo.add_check_item("배포"), 2);
+        assert_eq!(todo.add_check_item("공지"), 3);
+
+        assert_eq!(todo.toggle_check_item(1), Some(true));
+        assert_eq!(todo.toggle_check_item(0), None);
+        assert_eq!(todo.toggle_check_item(4), None);
+        assert_eq!(todo.checklist_progress(), (1, 3));
+
+        assert_eq!(todo.remove_check_item(2).map(|item| item.text), Some("배포".to_string()));
+        assert_eq!(todo.checklist_progress(), (1, 2));
+
+        // 반복 할일의 다음 회차는 체크리스트를 처음부터 다시 진행
+        todo.recurrence = Some(recurrence::Recurrence::Daily);
+        todo.toggle();
+        let next = todo.next_occurrence(2, Local::now()).unwrap();
+        assert_eq!(next.checklist_progress(), (0, 2));
+    }
+
+    // 첨부 파일이 내용 기준으로 저장되고 참조가 없으면 정리되는지 테스트
+    #[test]
+    fn test_attachment_store() {
+        let dir = std::env::temp_dir().join(format!("todo-attach-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("spec.PDF");
+        std::fs::write(&source, b"hello").unwrap();
+
+        let store = attachments::AttachmentStore::new(&dir);
+        let file = store.store(&source).unwrap();
+        // 같은 내용은 같은 이름으로 저장됨 (확장자는 소문자로)
+        assert_eq!(store.store(&source).unwrap(), file);
+        assert!(file.ends_with(".pdf"));
+        assert!(store.path(&file).exists());
+
+        let referenced = std::collections::HashSet::from([file.as_str()]);
+        assert_eq!(store.collect_garbage(&referenced).unwrap(), 0);
+        assert_eq!(store.collect_garbage(&std::collections::HashSet::new()).unwrap(), 1);
+        assert!(!store.path(&file).exists());
+
+        assert!(attachments::is_url("https://example.com/a"));
+        assert!(attachments::is_url("mailto:me@example.com"));
+        assert!(!attachments::is_url("./notes.txt"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // 번호와 UUID 앞부분으로 할일을 찾고, 겹치는 번호에 새 번호를 부여하는지 테스트
+    #[test]
+    fn test_resolve_key() {
+        let mut todos: Vec<todo::Todo> = (1..=3).map(|id| todo::Todo::new(id, format!("할일 {}", id))).collect();
+        todos[0].uuid = "3f2a0000-0000-4000-8000-000000000001".parse().unwrap();
+        todos[1].uuid = "3f2b0000-0000-4000-8000-000000000002".parse().unwrap();
+        todos[2].uuid = "a1000000-0000-4000-8000-000000000003".parse().unwrap();
+
+        // 숫자만으로 된 값은 항상 번호
+        assert_eq!(app::resolve_key(&todos, "2"), Ok(2));
+        assert_eq!(app::resolve_key(&todos, "3F2B"), Ok(2));
+        assert_eq!(app::resolve_key(&todos, "a1"), Ok(3));
+        // 여러 개와 일치하거나 하나도 없거나 UUID 형식이 아니면 에러
+        assert!(app::resolve_key(&todos, "3f2").is_err());
+        assert!(app::resolve_key(&todos, "ff").is_err());
+        assert!(app::resolve_key(&todos, "xyz").is_err());
+
+        // 두 파일을 합쳐 번호가 겹치면 뒤쪽 할일에 새 번호
+        todos[2].id = 1;
+        let (merged, renumbered) = app::merge_duplicates(&mut todos, 4);
+        assert!(merged.is_empty());
+        assert_eq!(renumbered, vec![(todos[2].uuid, 4)]);
+        assert_eq!(todos[2].id, 4);
+    }
+
+    // 같은 todos.json의 두 사본을 이어 붙이면 같은 할일은 합치고, 새 번호를 받은 할일을 가리키던 참조도 고치는지 테스트
+    #[test]
+    fn test_merge_duplicates() {
+        let original: Vec<todo::Todo> = (1..=2).map(|id| todo::Todo::new(id, format!("공통 {}", id))).collect();
+
+        // 첫 번째 사본: 3번 추가
+        let mut first = original.clone();
+        first.push(todo::Todo::new(3, "첫 사본".to_string()));
+        // 두 번째 사본: 2번 제목 수정, 3번과 그 하위 할일 4번 추가
+        let mut second = original.clone();
+        second[1].title = "공통 2 (수정)".to_string();
+        second[1].updated_at += Duration::minutes(1);
+        second.push(todo::Todo::new(3, "둘째 사본".to_string()));
+        let mut child = todo::Todo::new(4, "둘째 사본의 하위".to_string());
+        child.parent = Some(3);
+        child.depends_on.insert(3);
+        second.push(child);
+
+        let mut todos: Vec<todo::Todo> = first.into_iter().chain(second).collect();
+        let (merged, renumbered) = app::merge_duplicates(&mut todos, 5);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(renumbered.len(), 1);
+
+        let ids: Vec<usize> = todos.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 5, 4]);
+        assert_eq!(todos[1].title, "공통 2 (수정)");
+        // 둘째 사본의 3번은 5번이 되었으므로 하위 할일의 참조도 5번
+        assert_eq!(todos[4].parent, Some(5));
+        assert!(todos[4].depends_on.contains(&5));
+        let uuids: std::collections::HashSet<_> = todos.iter().map(|t| t.uuid).collect();
+        assert_eq!(uuids.len(), todos.len());
+    }
+
+    // UUID가 없는 예전 할일에만 UUID를 부여하는지 테스트
+    #[test]
+    fn test_assign_missing_uuids() {
+        let mut value = serde_json::json!([
+            { "id": 1, "title": "예전" },
+            { "id": 2, "title": "새것", "uuid": "3f2a0000-0000-4000-8000-000000000001" }
+        ]);
+        assert!(storage::assign_missing_uuids(&mut value));
+        assert!(value[0]["uuid"].is_string());
+        assert_eq!(value[1]["uuid"], "3f2a0000-0000-4000-8000-000000000001");
+        assert!(!storage::assign_missing_uuids(&mut value));
+    }
+
+    // 예전 버전의 파일을 현재 버전으로 올리고, 더 새로운 버전은 거부하는지 테스트
+    #[test]
+    fn test_upgrade() {
+        // 버전 0: 할일 배열만 있는 파일 - completed 변환과 UUID 부여까지 모두 적용
+        let upgraded = storage::upgrade(serde_json::json!([
+            { "id": 1, "title": "끝난 일", "completed": true,
+              "created_at": "2025-06-16T15:10:34+09:00", "updated_at": "2025-06-16T15:10:34+09:00" }
+        ]))
+        .unwrap();
+        assert_eq!((upgraded.next_id, upgraded.must_save), (None, true));
+        assert!(upgraded.todos[0]["uuid"].is_string());
+        let todos: Vec<todo::Todo> = serde_json::from_value(upgraded.todos).unwrap();
+        assert_eq!(todos[0].status, Status::Done);
+
+        // 버전 1: version 필드 없이 다음 ID가 있는 파일
+        let upgraded =
+            storage::upgrade(serde_json::json!({ "next_id": 8, "todos": [{ "id": 1, "uuid": "3f2a0000-0000-4000-8000-000000000001" }] }))
+                .unwrap();
+        assert_eq!((upgraded.next_id, upgraded.must_save), (Some(8), false));
+        assert_eq!(upgraded.todos.as_array().map(Vec::len), Some(1));
+
+        // 현재 버전은 그대로, 더 새로운 버전과 잘못된 형식은 에러
+        let current = serde_json::json!({ "version": storage::FORMAT_VERSION, "todos": [{ "id": 1, "title": "uuid 없음" }] });
+        let upgraded = storage::upgrade(current).unwrap();
+        assert!(!upgraded.must_save);
+        // UUID는 버전 0에서 올릴 때만 부여하므로, 현재 버전에 UUID가 없는 할일은 불러올 때 에러
+        assert!(serde_json::from_value::<Vec<todo::Todo>>(upgraded.todos).is_err());
+        let newer = serde_json::json!({ "version": storage::FORMAT_VERSION + 1, "todos": [] });
+        assert!(storage::upgrade(newer).unwrap_err().to_string().contains("업데이트"));
+        assert!(storage::upgrade(serde_json::json!("todos")).is_err());
+
+        // todos가 없거나 배열이 아니면, 또는 다음 ID가 숫자가 아니면 빈 목록으로 보지 않고 에러
+        let invalid = |value| storage::upgrade(value).unwrap_err().kind();
+        assert_eq!(invalid(serde_json::json!({ "version": 2 })), std::io::ErrorKind::InvalidData);
+        assert_eq!(invalid(serde_json::json!({ "version": 2, "todo": [] })), std::io::ErrorKind::InvalidData);
+        assert_eq!(invalid(serde_json::json!({ "version": 2, "todos": {} })), std::io::ErrorKind::InvalidData);
+        assert_eq!(invalid(serde_json::json!({ "next_id": "8", "todos": [] })), std::io::ErrorKind::InvalidData);
+    }
+
+    // 메모리 백엔드로 TodoApp을 만들고, 지운 ID가 다시 쓰이지 않는지 테스트
+    #[test]
+    fn test_memory_backend() {
+        use storage::Collection;
+
+        let memory = MemoryBackend::default();
+        let mut app = TodoApp::new(Box::new(memory.clone()), Config::default()).unwrap();
+        app.add("첫 번째".to_string(), AddOptions::default()).unwrap();
+        app.add("두 번째".to_string(), AddOptions::default()).unwrap();
+        app.delete(2, false).unwrap();
+        app.add("세 번째".to_string(), AddOptions::default()).unwrap();
+
+        let (todos, next_id) = memory.load(Collection::Todos).unwrap();
+        let ids: Vec<usize> = todos.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(next_id, Some(4));
+        assert_eq!(memory.load_todos(Collection::Trash).unwrap()[0].id, 2);
+
+        // 같은 데이터로 다시 만들어도 저장된 다음 ID를 이어서 사용
+        let mut app = TodoApp::new(Box::new(memory.clone()), Config::default()).unwrap();
+        app.purge_trash(None).unwrap();
+        app.add("네 번째".to_string(), AddOptions::default()).unwrap();
+        assert_eq!(memory.load_todos(Collection::Todos).unwrap().last().map(|t| t.id), Some(4));
+    }
+
+    // 검색 조건 테스트 - 태그, 상태, 마감 기한
+    #[test]
+    fn test_query_matches() {
+        use storage::Query;
+
+        let now = Local::now();
+        let mut todo = todo::Todo::new(1, "보고서".to_string());
+        todo.tags.insert("work".to_string());
+        todo.due = Some(now + Duration::days(2));
+
+        assert!(Query::default().matches(&todo));
+        assert!(Query { tag: Some("work".to_string()), ..Query::default() }.matches(&todo));
+        assert!(!Query { tag: Some("home".to_string()), ..Query::default() }.matches(&todo));
+        assert!(!Query { status: Some(Status::Done), ..Query::default() }.matches(&todo));
+        assert!(Query { due_before: Some(now + Duration::days(3)), ..Query::default() }.matches(&todo));
+        assert!(!Query { due_before: Some(now + Duration::days(1)), ..Query::default() }.matches(&todo));
+    }
+
+    // SQLite 백엔드 테스트 - JSON에서 옮긴 내용이 그대로이고 쿼리 결과가 메모리 필터와 같은지
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_backend() {
+        use storage::{Collection, Query};
+
+        let memory = MemoryBackend::default();
+        let mut app = TodoApp::new(Box::new(memory.clone()), Config::default()).unwrap();
+        let tomorrow = Local::now() + Duration::days(1);
+        let work = AddOptions { tags: vec!["work".to_string()], due: Some(tomorrow), ..AddOptions::default() };
+        app.add("첫 번째".to_string(), work).unwrap();
+        app.add("두 번째".to_string(), AddOptions::default()).unwrap();
+        app.set_status(2, Status::InProgress).unwrap();
+        app.delete(1, false).unwrap();
+        app.add("세 번째".to_string(), AddOptions { tags: vec!["work".to_string()], ..AddOptions::default() }).unwrap();
+
+        let database = sqlite::SqliteBackend::in_memory().unwrap();
+        assert_eq!(storage::copy_all(&memory, &database).unwrap(), 3);
+        for collection in Collection::ALL {
+            let expected = serde_json::to_value(memory.load(collection).unwrap()).unwrap();
+            let actual = serde_json::to_value(database.load(collection).unwrap()).unwrap();
+            assert_eq!(expected, actual);
+        }
+
+        let queries = [
+            Query { tag: Some("work".to_string()), ..Query::default() },
+            Query { status: Some(Status::InProgress), ..Query::default() },
+            Query { due_before: Some(tomorrow), ..Query::default() },
+        ];
+        for query in &queries {
+            for collection in Collection::ALL {
+                let ids = |todos: Vec<todo::Todo>| todos.iter().map(|t| t.id).collect::<Vec<_>>();
+                assert_eq!(
+                    ids(database.query(collection, query).unwrap()),
+                    ids(memory.query(collection, query).unwrap())
+                );
+            }
+        }
+    }
+
+    // 안전한 저장 테스트 - 덮어쓰면 이전 내용이 .bak으로 남고 임시 파일은 남지 않음
+    #[test]
+    fn test_write_atomic() {
+        let dir = std::env::temp_dir().join(format!("todo_cli_atomic_{}", process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("todos.json");
+
+        storage::write_atomic(&path, b"first").unwrap();
+        assert!(!dir.join("todos.json.bak").exists());
+        storage::write_atomic(&path, b"second").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second");
+        assert_eq!(std::fs::read_to_string(dir.join("todos.json.bak")).unwrap(), "first");
+
+        let mut names: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["todos.json", "todos.json.bak"]);
+
+        // 백업에 실패하면 원본은 그대로 두고 임시 파일도 남기지 않음
+        std::fs::remove_file(dir.join("todos.json.bak")).unwrap();
+        std::fs::create_dir(dir.join("todos.json.bak")).unwrap();
+        assert!(storage::write_atomic(&path, b"third").is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second");
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // 저장소 잠금 테스트 - 잠금이 잡혀 있으면 기다리다 실패하고, 풀리면 다시 잠글 수 있음
+    #[test]
+    fn test_lock_dir() {
+        let dir = std::env::temp_dir().join(format!("todo_cli_lock_{}", process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let timeout = std::time::Duration::from_millis(100);
+
+        let held = storage::lock_dir(&dir, timeout).unwrap();
+        let err = storage::lock_dir(&dir, timeout).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock);
+        drop(held);
+        assert!(storage::lock_dir(&dir, timeout).is_ok());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,40 @@
+// 알림 시각이 되었을 때 사용자에게 알리는 모듈
+use crate::todo::Todo;        // 알릴 할일
+use colored::*;               // 터미널 컬러 출력
+use std::io;                  // I/O 에러 타입
+use std::process::Command;    // 설정된 알림 명령 실행
+
+// 터미널 벨 문자 - 대부분의 터미널에서 소리나 깜빡임으로 알려줌
+const BELL: &str = "\x07";
+
+// 할일 알림 보내기
+// command가 있으면 셸에서 실행하고, 없으면 터미널에 벨과 함께 출력
+// 명령에서는 TODO_ID, TODO_TITLE, TODO_DUE 환경 변수를 사용할 수 있음
+pub fn send(todo: &Todo, command: Option<&str>) -> io::Result<()> {
+    let due = todo
+        .due
+        .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+
+    let Some(command) = command else {
+        let due_info = if due.is_empty() { String::new() } else { format!(" (마감: {})", due) };
+        println!("{}{}", BELL, format!("🔔 [{}] {}{}", todo.id, todo.title, due_info).yellow().bold());
+        return Ok(());
+    };
+
+    // 운영체제에 맞는 셸로 명령 실행
+    let (shell, flag) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+    let status = Command::new(shell)
+        .arg(flag)
+        .arg(command)
+        .env("TODO_ID", todo.id.to_string())
+        .env("TODO_TITLE", &todo.title)
+        .env("TODO_DUE", due)
+        .status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("알림 명령이 실패했습니다: {}", status)))
+    }
+}
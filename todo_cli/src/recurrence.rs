@@ -0,0 +1,145 @@
+// 반복 규칙을 정의하고 다음 마감일을 계산하는 모듈
+use crate::dates;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// 반복 규칙
+// JSON에는 {"kind": "weekly", "days": ["Mon", "Thu"]} 형태로 저장
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Recurrence {
+    Daily,                               // 매일
+    Weekly { days: Vec<Weekday> },       // 매주 지정한 요일들 (비어 있으면 마감일과 같은 요일)
+    Monthly { day: u32 },                // 매월 N일 (그 달에 없는 날짜면 말일)
+    AfterCompletion { days: u32 },       // 완료한 날로부터 N일 뒤
+}
+
+impl Recurrence {
+    // 다음 마감일 계산
+    // due: 현재 마감일 (없을 수 있음), completed_at: 완료한 시각
+    // 일정 기반 규칙(매일/매주/매월)은 일찍 끝내도 일정이 밀리지 않도록
+    // 현재 마감일과 완료 시각 중 늦은 쪽 이후의 첫 일정을 찾음
+    // 마감일이 없으면 완료한 날이 이번 회차이므로 그다음 날부터 찾음
+    pub fn next_due(&self, due: Option<DateTime<Local>>, completed_at: DateTime<Local>) -> Option<DateTime<Local>> {
+        // 시각은 기존 마감일의 시각을 유지 (없으면 하루의 끝)
+        let time = due.map(|d| d.time()).unwrap_or_else(dates::end_of_day_time);
+        let current = due.or_else(|| dates::at_time(completed_at.date_naive(), time));
+        let anchor = current.map_or(completed_at, |d| d.max(completed_at));
+
+        // anchor 이후의 첫 날짜를 찾는 도우미 - 같은 날이라도 시각이 anchor 이후면 허용
+        let first_after = |candidate: NaiveDate| {
+            dates::at_time(candidate, time).filter(|next| *next > anchor)
+        };
+
+        match self {
+            Recurrence::Daily => {
+                let start = anchor.date_naive();
+                (0..=1).find_map(|offset| first_after(start + Duration::days(offset)))
+            }
+            Recurrence::Weekly { days } => {
+                // 요일이 지정되지 않았으면 마감일(없으면 완료일)과 같은 요일
+                let days = if days.is_empty() {
+                    vec![due.unwrap_or(completed_at).weekday()]
+                } else {
+                    days.clone()
+                };
+                let start = anchor.date_naive();
+                (0..=7)
+                    .map(|offset| start + Duration::days(offset))
+                    .filter(|date| days.contains(&date.weekday()))
+                    .find_map(first_after)
+            }
+            Recurrence::Monthly { day } => {
+                // 이번 달과 다음 달 중 anchor 이후의 첫 N일
+                let start = anchor.date_naive();
+                (0..=2)
+                    .filter_map(|offset| month_day(start.year(), start.month() + offset, *day))
+                    .find_map(first_after)
+            }
+            Recurrence::AfterCompletion { days } => {
+                let date = completed_at.date_naive() + Duration::days(i64::from(*days));
+                dates::at_time(date, time)
+            }
+        }
+    }
+}
+
+// year년 month월의 day일 (달이 12를 넘으면 다음 해로, 날짜가 없으면 그 달의 말일)
+fn month_day(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    let year = year + ((month - 1) / 12) as i32;
+    let month = (month - 1) % 12 + 1;
+    // 31일부터 내려가며 그 달에 존재하는 날짜를 찾음
+    (1..=day.min(31))
+        .rev()
+        .find_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+}
+
+// 반복 규칙 문자열 파싱
+// 지원 형식:
+//   daily                 - 매일
+//   weekly                - 매주 (마감일과 같은 요일)
+//   weekly:mon,thu        - 매주 월, 목
+//   monthly:15            - 매월 15일
+//   after:3               - 완료한 날로부터 3일 뒤
+// clap의 value_parser로도 사용하기 위해 에러를 String으로 반환
+pub fn parse(input: &str) -> Result<Recurrence, String> {
+    let input = input.trim().to_lowercase();
+    let (kind, arg) = match input.split_once(':') {
+        Some((kind, arg)) => (kind, Some(arg)),
+        None => (input.as_str(), None),
+    };
+
+    match (kind, arg) {
+        ("daily", None) => Ok(Recurrence::Daily),
+        ("weekly", None) => Ok(Recurrence::Weekly { days: Vec::new() }),
+        ("weekly", Some(days)) => {
+            let days = days
+                .split(',')
+                .map(|d| d.trim().parse::<Weekday>().map_err(|_| format!("알 수 없는 요일입니다: '{}'", d)))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Recurrence::Weekly { days })
+        }
+        ("monthly", Some(day)) => match day.parse::<u32>() {
+            Ok(day @ 1..=31) => Ok(Recurrence::Monthly { day }),
+            _ => Err(format!("날짜는 1~31 사이여야 합니다: '{}'", day)),
+        },
+        ("after", Some(days)) => match days.trim_end_matches('d').parse::<u32>() {
+            Ok(days) if days > 0 => Ok(Recurrence::AfterCompletion { days }),
+            _ => Err(format!("일 수는 1 이상의 숫자여야 합니다: '{}'", days)),
+        },
+        _ => Err(format!(
+            "반복 규칙을 알 수 없습니다: '{}' (예: daily, weekly, weekly:mon,thu, monthly:15, after:3)",
+            input
+        )),
+    }
+}
+
+// 목록에 표시할 반복 규칙 설명
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Recurrence::Daily => write!(f, "매일"),
+            Recurrence::Weekly { days } if days.is_empty() => write!(f, "매주"),
+            Recurrence::Weekly { days } => {
+                let names: Vec<&str> = days.iter().map(|d| weekday_name(*d)).collect();
+                write!(f, "매주 {}", names.join(","))
+            }
+            Recurrence::Monthly { day } => write!(f, "매월 {}일", day),
+            Recurrence::AfterCompletion { days } => write!(f, "완료 {}일 후", days),
+        }
+    }
+}
+
+// 요일의 한글 이름
+fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "월",
+        Weekday::Tue => "화",
+        Weekday::Wed => "수",
+        Weekday::Thu => "목",
+        Weekday::Fri => "금",
+        Weekday::Sat => "토",
+        Weekday::Sun => "일",
+    }
+}
@@ -0,0 +1,191 @@
+// SQLite 저장소 백엔드 (cargo build --features sqlite)
+// 할일 전체는 JSON 그대로 data 열에 저장해서 손실 없이 되돌릴 수 있고,
+// 상태, 마감 기한, 태그는 따로 인덱스가 있는 열/테이블에 두어 조건 검색을 쿼리로 실행
+use crate::storage::{self, Backend, Collection, Loaded, Query, StorageLock}; // 백엔드 트레이트
+use crate::todo::Todo;        // Todo 데이터 구조
+use rusqlite::types::ToSql;   // 동적으로 만드는 쿼리 인자
+use rusqlite::{Connection, params, params_from_iter}; // SQLite 연결과 쿼리 인자
+use std::io;                  // I/O 에러 타입
+use std::path::{Path, PathBuf}; // 파일 경로 처리
+use std::time::Duration;      // 잠금 대기 시간
+
+// 데이터베이스 파일명 - todos.json과 같은 위치
+pub const DATABASE_FILE: &str = "todos.db";
+
+// 테이블과 인덱스 - 이미 있으면 그대로 사용
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS todos (
+        collection TEXT NOT NULL,
+        ord        INTEGER NOT NULL,
+        id         INTEGER NOT NULL,
+        uuid       TEXT NOT NULL,
+        status     TEXT NOT NULL,
+        due        INTEGER,
+        data       TEXT NOT NULL,
+        PRIMARY KEY (collection, uuid)
+    );
+    CREATE INDEX IF NOT EXISTS todos_status ON todos (collection, status);
+    CREATE INDEX IF NOT EXISTS todos_due ON todos (collection, due);
+    CREATE TABLE IF NOT EXISTS tags (
+        collection TEXT NOT NULL,
+        uuid       TEXT NOT NULL,
+        tag        TEXT NOT NULL,
+        PRIMARY KEY (collection, uuid, tag)
+    );
+    CREATE INDEX IF NOT EXISTS tags_tag ON tags (collection, tag);
+    CREATE TABLE IF NOT EXISTS meta (
+        collection TEXT PRIMARY KEY,
+        next_id    INTEGER
+    );
+";
+
+// SqliteBackend 구조체 - 묶음(목록, 보관함, 휴지통)은 collection 열로 구분
+pub struct SqliteBackend {
+    conn: Connection,     // 데이터베이스 연결
+    dir: PathBuf,         // 데이터베이스 파일이 있는 디렉터리 (첨부 파일도 이 아래에 보관)
+}
+
+impl SqliteBackend {
+    // 현재 디렉터리의 todos.db를 열거나 새로 만듦
+    pub fn open() -> Result<Self, io::Error> {
+        Self::open_at(Path::new(DATABASE_FILE))
+    }
+
+    // 주어진 경로의 데이터베이스를 열거나 새로 만듦 (migrate가 임시 파일에 옮길 때 사용)
+    pub fn open_at(path: &Path) -> Result<Self, io::Error> {
+        Self::with_connection(Connection::open(path).map_err(to_io)?)
+    }
+
+    // 파일 없이 메모리에만 있는 데이터베이스 (테스트용)
+    #[cfg(test)]
+    pub fn in_memory() -> Result<Self, io::Error> {
+        Self::with_connection(Connection::open_in_memory().map_err(to_io)?)
+    }
+
+    // 스키마를 만들고 백엔드 생성
+    fn with_connection(conn: Connection) -> Result<Self, io::Error> {
+        conn.execute_batch(SCHEMA).map_err(to_io)?;
+        Ok(Self { conn, dir: PathBuf::new() })
+    }
+
+    // 현재 디렉터리에 데이터베이스 파일이 있는지 확인
+    pub fn exists() -> bool {
+        Path::new(DATABASE_FILE).exists()
+    }
+}
+
+impl Backend for SqliteBackend {
+    fn load(&self, collection: Collection) -> Result<Loaded, io::Error> {
+        let todos = self.query(collection, &Query::default())?;
+        let next_id: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT next_id FROM meta WHERE collection = ?1",
+                params![collection.name()],
+                |row| row.get(0),
+            )
+            .or_else(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                e => Err(to_io(e)),
+            })?;
+        Ok((todos, next_id.map(|id| id as usize)))
+    }
+
+    // 묶음 전체를 하나의 트랜잭션으로 교체 - 중간에 실패하면 이전 내용이 그대로 남음
+    fn save(&self, collection: Collection, todos: &[Todo], next_id: Option<usize>) -> Result<(), io::Error> {
+        let name = collection.name();
+        let tx = self.conn.unchecked_transaction().map_err(to_io)?;
+        tx.execute("DELETE FROM todos WHERE collection = ?1", params![name]).map_err(to_io)?;
+        tx.execute("DELETE FROM tags WHERE collection = ?1", params![name]).map_err(to_io)?;
+        {
+            let mut insert_todo = tx
+                .prepare("INSERT INTO todos (collection, ord, id, uuid, status, due, data) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)")
+                .map_err(to_io)?;
+            let mut insert_tag = tx
+                .prepare("INSERT INTO tags (collection, uuid, tag) VALUES (?1, ?2, ?3)")
+                .map_err(to_io)?;
+            for (ord, todo) in todos.iter().enumerate() {
+                let uuid = todo.uuid.to_string();
+                let data = serde_json::to_string(todo).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                insert_todo
+                    .execute(params![
+                        name,
+                        ord as i64,
+                        todo.id as i64,
+                        uuid,
+                        status_name(todo),
+                        todo.due.map(|d| d.timestamp()),
+                        data,
+                    ])
+                    .map_err(to_io)?;
+                for tag in &todo.tags {
+                    insert_tag.execute(params![name, uuid, tag]).map_err(to_io)?;
+                }
+            }
+        }
+        tx.execute(
+            "INSERT INTO meta (collection, next_id) VALUES (?1, ?2)
+             ON CONFLICT (collection) DO UPDATE SET next_id = excluded.next_id",
+            params![name, next_id.map(|id| id as i64)],
+        )
+        .map_err(to_io)?;
+        tx.commit().map_err(to_io)
+    }
+
+    fn dir(&self) -> Option<&Path> {
+        Some(&self.dir)
+    }
+
+    // SQLite 자체 잠금은 트랜잭션 하나만 보호하므로, 불러오기-수정-저장 전체는 JSON과 같은 잠금 파일로 보호
+    fn lock(&self, timeout: Duration) -> Result<StorageLock, io::Error> {
+        storage::lock_dir(&self.dir, timeout)
+    }
+
+    // 조건을 WHERE 절로 바꿔서 인덱스를 사용해 검색
+    fn query(&self, collection: Collection, query: &Query) -> Result<Vec<Todo>, io::Error> {
+        let mut sql = String::from("SELECT data FROM todos t WHERE collection = ?");
+        let mut args: Vec<Box<dyn ToSql>> = vec![Box::new(collection.name())];
+        if let Some(status) = query.status {
+            sql.push_str(" AND status = ?");
+            args.push(Box::new(serde_name(&status)));
+        }
+        if let Some(tag) = &query.tag {
+            sql.push_str(" AND EXISTS (SELECT 1 FROM tags g WHERE g.collection = t.collection AND g.uuid = t.uuid AND g.tag = ?)");
+            args.push(Box::new(tag.clone()));
+        }
+        if let Some(before) = query.due_before {
+            sql.push_str(" AND due IS NOT NULL AND due <= ?");
+            args.push(Box::new(before.timestamp()));
+        }
+        sql.push_str(" ORDER BY ord");
+
+        let mut stmt = self.conn.prepare(&sql).map_err(to_io)?;
+        let rows = stmt
+            .query_map(params_from_iter(args.iter()), |row| row.get::<_, String>(0))
+            .map_err(to_io)?;
+        let mut todos = Vec::new();
+        for data in rows {
+            let data = data.map_err(to_io)?;
+            todos.push(serde_json::from_str(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?);
+        }
+        Ok(todos)
+    }
+}
+
+// 상태를 JSON에 저장되는 이름 그대로 사용 (예: "in-progress")
+fn status_name(todo: &Todo) -> String {
+    serde_name(&todo.status)
+}
+
+// serde로 직렬화했을 때의 문자열 값
+fn serde_name<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+// SQLite 에러를 I/O 에러로 변환 (Backend 트레이트의 에러 타입에 맞춤)
+fn to_io(e: rusqlite::Error) -> io::Error {
+    io::Error::other(e)
+}
@@ -1,60 +1,421 @@
 // 필요한 모듈과 타입 임포트
-use crate::todo::Todo;    // Todo 데이터 구조
-use std::fs;              // 파일 시스템 작업
-use std::io;              // I/O 에러 타입
-use std::path::Path;      // 파일 경로 처리
+use crate::todo::{Status, Todo}; // Todo 데이터 구조, 진행 상태
+use chrono::{DateTime, Local}; // 마감 기한 조건
+use serde::Serialize;      // 저장 파일 형식 직렬화
+use serde_json::Value;    // 역직렬화 전에 예전 형식을 변환하기 위한 JSON 값
+#[cfg(test)]
+use std::cell::RefCell;   // 메모리 백엔드의 내부 가변성
+#[cfg(test)]
+use std::collections::HashMap; // 묶음별 할일 목록 (메모리 백엔드)
+use std::fs::{self, File, OpenOptions, TryLockError}; // 파일 시스템 작업, 파일 잠금
+use std::io::{self, Write}; // I/O 에러 타입, 임시 파일 쓰기
+use std::path::{Path, PathBuf}; // 파일 경로 처리
+use std::process;         // 임시 파일 이름에 쓸 프로세스 ID
+#[cfg(test)]
+use std::rc::Rc;          // 메모리 백엔드 복제본끼리 데이터 공유
+use std::thread;          // 잠금을 기다리는 동안 대기
+use std::time::{Duration as StdDuration, Instant}; // 잠금 대기 시간
+use uuid::Uuid;           // 예전 할일에 부여할 고유 식별자
 
 // 상수 정의 - 데이터를 저장할 파일명
 const STORAGE_FILE: &str = "todos.json";
+// 보관함 파일명 - clear로 정리된 할일을 저장 파일 옆에 따로 보관
+const ARCHIVE_FILE: &str = "todos_archive.json";
+// 휴지통 파일명 - delete로 지운 할일을 비우기 전까지 보관
+const TRASH_FILE: &str = "todos_trash.json";
+// 잠금 파일명 - 저장 파일은 저장할 때마다 새 파일로 바뀌므로 잠금은 따로 둔 파일에 걸어야 함
+const LOCK_FILE: &str = "todos.lock";
+// 잠금이 풀렸는지 다시 확인하는 간격
+const LOCK_RETRY: StdDuration = StdDuration::from_millis(50);
 
-// Storage 구조체 - 파일 기반 영속성을 담당
-pub struct Storage {
-    file_path: String,    // 저장 파일의 경로
+// 백엔드가 따로 보관하는 할일 묶음
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Collection {
+    Todos,                // 현재 할일 목록
+    Archive,              // clear로 정리된 할일 보관함
+    Trash,                // delete로 지운 할일 휴지통
 }
 
-// Storage의 메서드 구현
-impl Storage {
-    // 새로운 Storage 인스턴스 생성
-    pub fn new() -> Self {
-        Self {
-            // &str을 String으로 변환
-            file_path: STORAGE_FILE.to_string(),
+// 묶음 목록과 이름은 다른 저장소로 옮길 때(migrate)만 필요
+#[cfg(feature = "sqlite")]
+impl Collection {
+    pub const ALL: [Collection; 3] = [Collection::Todos, Collection::Archive, Collection::Trash];
+
+    // 저장소 안에서 쓰는 이름
+    pub fn name(&self) -> &'static str {
+        match self {
+            Collection::Todos => "todos",
+            Collection::Archive => "archive",
+            Collection::Trash => "trash",
         }
     }
+}
+
+// 할일 검색 조건 (None인 조건은 적용하지 않음)
+// list는 불러온 목록에서 거르고, SQLite 백엔드는 같은 조건을 인덱스를 쓰는 쿼리로 실행
+#[derive(Debug, Default, Clone)]
+pub struct Query {
+    pub status: Option<Status>,              // 이 상태인 할일만
+    pub tag: Option<String>,                 // 이 태그가 붙은 할일만
+    pub due_before: Option<DateTime<Local>>, // 이 시각까지 마감인 할일만
+}
+
+impl Query {
+    // 할일이 모든 조건을 만족하는지 확인
+    pub fn matches(&self, todo: &Todo) -> bool {
+        self.status.is_none_or(|status| todo.status == status)
+            && self.tag.as_deref().is_none_or(|tag| todo.has_tag(tag))
+            && self.due_before.is_none_or(|before| todo.due.is_some_and(|due| due <= before))
+    }
+}
+
+// 불러온 할일 목록과 저장된 다음 ID
+pub type Loaded = (Vec<Todo>, Option<usize>);
+
+// 저장소 백엔드 트레이트 - TodoApp은 이 트레이트를 통해서만 데이터를 읽고 씀
+pub trait Backend {
+    // 할일 목록과 저장된 다음 ID를 불러옴 (저장된 것이 없으면 빈 목록과 None)
+    fn load(&self, collection: Collection) -> Result<Loaded, io::Error>;
+
+    // 할일 목록과 다음에 발급할 ID를 저장 (ID를 발급하지 않는 묶음은 None)
+    fn save(&self, collection: Collection, todos: &[Todo], next_id: Option<usize>) -> Result<(), io::Error>;
+
+    // 첨부 파일을 함께 보관할 디렉터리 (파일에 저장하지 않는 백엔드는 None)
+    fn dir(&self) -> Option<&Path> {
+        None
+    }
+
+    // 다른 todo 프로세스가 같은 저장소를 동시에 고치지 못하도록 잠금 (기본 구현: 잠그지 않음)
+    // 반환된 StorageLock이 살아 있는 동안 잠금이 유지됨
+    fn lock(&self, _timeout: StdDuration) -> Result<StorageLock, io::Error> {
+        Ok(StorageLock { _file: None })
+    }
+
+    // 할일 목록만 불러옴
+    fn load_todos(&self, collection: Collection) -> Result<Vec<Todo>, io::Error> {
+        Ok(self.load(collection)?.0)
+    }
+
+    // 조건에 맞는 할일만 불러옴 (기본 구현: 모두 불러온 뒤 메모리에서 거름)
+    #[cfg(feature = "sqlite")]
+    fn query(&self, collection: Collection, query: &Query) -> Result<Vec<Todo>, io::Error> {
+        let mut todos = self.load_todos(collection)?;
+        todos.retain(|t| query.matches(t));
+        Ok(todos)
+    }
+}
 
-    // 파일에서 Todo 목록을 불러오는 메서드
-    // Result<Vec<Todo>, io::Error>: 성공시 Todo 벡터, 실패시 IO 에러 반환
-    pub fn load(&self) -> Result<Vec<Todo>, io::Error> {
+// 저장소 잠금 - drop되면 (파일이 닫히면서) 잠금이 풀림
+// 프로세스가 비정상 종료되어도 운영체제가 잠금을 풀어 주므로 남아 있는 잠금 파일은 그대로 재사용
+#[derive(Debug)]
+pub struct StorageLock {
+    _file: Option<File>,  // 잠금을 건 파일 (잠그지 않는 백엔드는 None)
+}
+
+// dir 안의 잠금 파일에 배타적 잠금을 걸고, 다른 프로세스가 잡고 있으면 timeout까지 기다림
+// 끝내 잠그지 못하면 ErrorKind::WouldBlock 에러
+pub fn lock_dir(dir: &Path, timeout: StdDuration) -> Result<StorageLock, io::Error> {
+    let path = dir.join(LOCK_FILE);
+    let file = OpenOptions::new().create(true).truncate(false).write(true).open(&path)?;
+    let started = Instant::now();
+    loop {
+        match file.try_lock() {
+            Ok(()) => return Ok(StorageLock { _file: Some(file) }),
+            Err(TryLockError::Error(e)) => return Err(e),
+            Err(TryLockError::WouldBlock) if started.elapsed() < timeout => thread::sleep(LOCK_RETRY),
+            Err(TryLockError::WouldBlock) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::WouldBlock,
+                    format!(
+                        "다른 todo 명령이 저장소를 사용 중입니다 ({}초 동안 기다림). 잠시 후 다시 시도하세요. (잠금 파일: {})",
+                        timeout.as_secs_f64(),
+                        path.display()
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+// 한 백엔드의 모든 묶음을 다른 백엔드로 그대로 복사 - 복사한 할일 수를 반환 (migrate에 사용)
+// 에러 메시지에는 실패한 묶음 이름을 붙임
+#[cfg(feature = "sqlite")]
+pub fn copy_all(source: &dyn Backend, target: &dyn Backend) -> Result<usize, io::Error> {
+    let mut copied = 0;
+    for collection in Collection::ALL {
+        let with_name = |e: io::Error| io::Error::new(e.kind(), format!("{}: {}", collection.name(), e));
+        let (todos, next_id) = source.load(collection).map_err(with_name)?;
+        target.save(collection, &todos, next_id).map_err(with_name)?;
+        copied += todos.len();
+    }
+    Ok(copied)
+}
+
+// JsonBackend 구조체 - JSON 파일 기반 영속성을 담당
+// 묶음마다 같은 디렉터리의 파일 하나를 사용 (todos.json, todos_archive.json, todos_trash.json)
+pub struct JsonBackend {
+    dir: PathBuf,         // 저장 파일들이 있는 디렉터리 (비어 있으면 현재 디렉터리)
+}
+
+// JsonBackend의 메서드 구현
+impl JsonBackend {
+    // 현재 디렉터리의 파일을 사용하는 JsonBackend 생성
+    pub fn new() -> Self {
+        Self { dir: PathBuf::new() }
+    }
+
+    // 묶음별 저장 파일 경로
+    fn path(&self, collection: Collection) -> PathBuf {
+        let file_name = match collection {
+            Collection::Todos => STORAGE_FILE,
+            Collection::Archive => ARCHIVE_FILE,
+            Collection::Trash => TRASH_FILE,
+        };
+        self.dir.join(file_name)
+    }
+
+    // { "version": ..., "saved_at": ..., "next_id": ..., "todos": [...] } 형식으로 파일에 쓰기
+    fn write(&self, path: &Path, todos: &[Todo], next_id: Option<usize>) -> Result<(), io::Error> {
+        // 할일 목록을 메타데이터와 함께 보기 좋은 JSON 문자열로 직렬화
+        // to_string_pretty()는 들여쓰기가 있는 읽기 쉬운 형식 생성
+        let envelope = Envelope {
+            version: FORMAT_VERSION,
+            saved_at: Local::now(),
+            next_id,
+            todos,
+        };
+        let json = serde_json::to_string_pretty(&envelope)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        
+        // JSON 문자열을 파일에 쓰기
+        // 중간에 멈춰도 잘린 파일이 남지 않도록 임시 파일에 쓴 뒤 바꿔치기
+        write_atomic(path, json.as_bytes())
+    }
+}
+
+impl Backend for JsonBackend {
+    // 파일에서 Todo 목록과 저장된 다음 ID를 함께 불러오는 메서드
+    // 예전 버전의 파일은 변환해서 읽음 (할일 배열만 있는 파일은 다음 ID가 None)
+    fn load(&self, collection: Collection) -> Result<Loaded, io::Error> {
         // 파일이 존재하지 않으면 빈 벡터 반환
-        // Path::new()를 사용하여 파일 존재 여부 확인
-        if !Path::new(&self.file_path).exists() {
-            return Ok(Vec::new());
+        let path = self.path(collection);
+        if !path.exists() {
+            return Ok((Vec::new(), None));
         }
 
         // 파일 내용을 문자열로 읽기
         // ?를 사용하여 에러 발생시 즉시 반환
-        let contents = fs::read_to_string(&self.file_path)?;
+        let contents = fs::read_to_string(&path)?;
         
-        // JSON 문자열을 Todo 벡터로 역직렬화
+        // JSON 문자열을 먼저 JSON 값으로 읽어 예전 형식을 현재 버전으로 올린 뒤 Todo 벡터로 역직렬화
         // serde_json의 에러를 io::Error로 변환
-        let todos: Vec<Todo> = serde_json::from_str(&contents)
+        let value: Value = serde_json::from_str(&contents)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let upgraded = upgrade(value)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        let next_id = upgraded.next_id;
+        let todos: Vec<Todo> = serde_json::from_value(upgraded.todos)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e)))?;
+
+        // 새로 부여한 UUID처럼 실행할 때마다 달라지는 값은 바로 저장해야 다음 실행에서도 같은 값으로 가리킬 수 있음
+        // 나머지 변환은 다음에 저장할 때 현재 형식으로 기록됨
+        if upgraded.must_save {
+            self.write(&path, &todos, next_id)?;
+        }
         
-        Ok(todos)
+        Ok((todos, next_id))
     }
 
     // Todo 목록을 파일에 저장하는 메서드
-    // &[Todo]: Todo 슬라이스 참조 (읽기 전용)
-    pub fn save(&self, todos: &[Todo]) -> Result<(), io::Error> {
-        // Todo 벡터를 보기 좋은 JSON 문자열로 직렬화
-        // to_string_pretty()는 들여쓰기가 있는 읽기 쉬운 형식 생성
-        let json = serde_json::to_string_pretty(todos)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
-        
-        // JSON 문자열을 파일에 쓰기
-        // fs::write()는 파일이 없으면 생성, 있으면 덮어쓰기
-        fs::write(&self.file_path, json)?;
-        
+    // 다음 ID를 저장해 두어야 가장 최근 할일을 지워도 그 ID가 다시 쓰이지 않음
+    fn save(&self, collection: Collection, todos: &[Todo], next_id: Option<usize>) -> Result<(), io::Error> {
+        self.write(&self.path(collection), todos, next_id)
+    }
+
+    // 세 저장 파일을 한 번에 잠금 (보관함, 휴지통도 목록과 함께 바뀌기 때문)
+    fn lock(&self, timeout: StdDuration) -> Result<StorageLock, io::Error> {
+        lock_dir(&self.dir, timeout)
+    }
+
+    // 저장 파일이 있는 디렉터리 (첨부 파일도 이 아래에 보관)
+    fn dir(&self) -> Option<&Path> {
+        Some(&self.dir)
+    }
+}
+
+// MemoryBackend 구조체 - 파일에 쓰지 않고 메모리에만 보관하는 백엔드 (테스트에서 TodoApp을 만들 때 사용)
+// 복제본끼리 같은 데이터를 공유하므로 TodoApp에 넘긴 뒤에도 저장된 내용을 확인할 수 있음
+#[cfg(test)]
+#[derive(Debug, Default, Clone)]
+pub struct MemoryBackend {
+    collections: Rc<RefCell<HashMap<Collection, Loaded>>>,
+}
+
+#[cfg(test)]
+impl Backend for MemoryBackend {
+    fn load(&self, collection: Collection) -> Result<Loaded, io::Error> {
+        Ok(self.collections.borrow().get(&collection).cloned().unwrap_or_default())
+    }
+
+    fn save(&self, collection: Collection, todos: &[Todo], next_id: Option<usize>) -> Result<(), io::Error> {
+        self.collections.borrow_mut().insert(collection, (todos.to_vec(), next_id));
+        Ok(())
+    }
+}
+
+// 파일을 안전하게 덮어쓰기
+// 같은 디렉터리의 임시 파일에 쓰고 디스크에 반영(fsync)한 뒤 원래 이름으로 바꿈 (rename은 원자적)
+// 기존 파일은 "이름.bak"으로 남겨 두어 한 단계 전 내용으로 되돌릴 수 있음
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), io::Error> {
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "파일 이름이 없습니다"))?;
+    let temp = path.with_file_name(format!(".{}.{}.tmp", file_name, process::id()));
+
+    // 임시 파일에 쓰고 fsync - 실패하면 임시 파일만 지우고 원본은 그대로
+    let written = File::create(&temp).and_then(|mut file| {
+        file.write_all(contents)?;
+        file.sync_all()
+    });
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp);
+        return Err(e);
+    }
+
+    // 바꾸기 전에 이전 내용을 백업하고 바꿔치기 - 어느 쪽이든 실패하면 임시 파일을 지움
+    let backed_up = if path.exists() {
+        fs::copy(path, path.with_file_name(format!("{}.bak", file_name))).map(|_| ())
+    } else {
         Ok(())
+    };
+    let replaced = backed_up.and_then(|()| fs::rename(&temp, path));
+    if let Err(e) = replaced {
+        let _ = fs::remove_file(&temp);
+        return Err(e);
     }
-}
\ No newline at end of file
+
+    // 이름 바꾸기까지 디스크에 반영되도록 디렉터리도 fsync (지원하지 않는 플랫폼은 무시)
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    if let Ok(dir) = File::open(dir) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+// 현재 저장 파일 형식 버전
+// 0: 할일 배열만 있는 파일 (오래된 것은 completed 불린 값을 쓰고 UUID가 없음)
+// 1: { "next_id": ..., "todos": [...] } (version 필드 없음, 모든 할일에 status와 UUID가 있음)
+// 2: version, saved_at 메타데이터 추가
+pub const FORMAT_VERSION: u64 = 2;
+
+// 버전을 하나씩 올리는 변환 단계 - MIGRATIONS[n]은 버전 n의 할일 배열을 n+1로 바꿈
+// 반환값: 변환 결과를 바로 저장해야 하는지 (UUID처럼 실행할 때마다 달라지는 값을 만든 경우)
+const MIGRATIONS: [fn(&mut Value) -> bool; FORMAT_VERSION as usize] = [
+    // 0 → 1: completed를 status로 바꾸고 UUID가 없는 할일에 UUID 부여
+    |todos| {
+        migrate_completed_flag(todos);
+        assign_missing_uuids(todos)
+    },
+    // 1 → 2: 할일 배열은 그대로 (version, saved_at은 다음에 저장할 때 기록됨)
+    |_| false,
+];
+
+// 저장 파일의 형식 - 할일 목록과 메타데이터
+#[derive(Serialize)]
+struct Envelope<'a> {
+    version: u64,             // 저장 형식 버전 (FORMAT_VERSION)
+    saved_at: DateTime<Local>, // 마지막으로 저장한 시각
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_id: Option<usize>,   // 다음에 발급할 ID (한 번 쓴 ID는 다시 쓰지 않음)
+    todos: &'a [Todo],        // 할일 목록
+}
+
+// 현재 버전으로 올린 파일 내용
+#[derive(Debug)]
+pub struct Upgraded {
+    pub todos: Value,         // 현재 형식의 할일 배열
+    pub next_id: Option<usize>, // 저장된 다음 ID (예전 파일은 None)
+    pub must_save: bool,      // 변환 결과를 바로 저장해야 하는지
+}
+
+// 파일 내용의 버전을 확인하고 현재 버전까지 변환 단계를 차례로 적용
+// 이 프로그램보다 새로운 버전의 파일은 내용을 잃지 않도록 읽지 않음
+pub fn upgrade(value: Value) -> Result<Upgraded, io::Error> {
+    let (mut todos, next_id, from_version) = match value {
+        Value::Array(_) => (value, None, 0),
+        Value::Object(mut object) => {
+            let version = match object.get("version") {
+                None => 1,
+                Some(version) => version
+                    .as_u64()
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "저장 형식 버전이 숫자가 아닙니다"))?,
+            };
+            // 빠진 필드를 빈 목록으로 보면 다음 저장 때 파일이 비워지므로 형식이 다르면 에러
+            let next_id = match object.get("next_id") {
+                None | Some(Value::Null) => None,
+                Some(next_id) => Some(
+                    next_id
+                        .as_u64()
+                        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "다음 ID가 숫자가 아닙니다"))?
+                        as usize,
+                ),
+            };
+            let todos = match object.remove("todos") {
+                Some(todos @ Value::Array(_)) => todos,
+                Some(_) => return Err(io::Error::new(io::ErrorKind::InvalidData, "todos가 할일 배열이 아닙니다")),
+                None => return Err(io::Error::new(io::ErrorKind::InvalidData, "할일 목록(todos)이 없습니다")),
+            };
+            (todos, next_id, version)
+        }
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "할일 목록 형식이 아닙니다")),
+    };
+
+    if from_version > FORMAT_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "더 새로운 버전의 todo로 저장된 파일입니다 (형식 버전 {}, 이 프로그램은 {}까지 지원). todo를 업데이트하세요.",
+                from_version, FORMAT_VERSION
+            ),
+        ));
+    }
+
+    let mut must_save = false;
+    for migration in &MIGRATIONS[from_version as usize..] {
+        must_save |= migration(&mut todos);
+    }
+    Ok(Upgraded { todos, next_id, must_save })
+}
+
+// 예전 형식의 "completed": true/false 를 "status": "done"/"todo" 로 변환
+// 이미 status가 있는 항목은 그대로 두고, completed 필드만 제거
+pub fn migrate_completed_flag(value: &mut Value) {
+    let Some(todos) = value.as_array_mut() else {
+        return;
+    };
+    for todo in todos.iter_mut().filter_map(Value::as_object_mut) {
+        if let Some(completed) = todo.remove("completed") {
+            let status = if completed.as_bool() == Some(true) { "done" } else { "todo" };
+            todo.entry("status").or_insert_with(|| Value::from(status));
+        }
+    }
+}
+
+// UUID가 없는 예전 할일에 새 UUID를 부여 - 하나라도 부여했으면 true
+pub fn assign_missing_uuids(value: &mut Value) -> bool {
+    let Some(todos) = value.as_array_mut() else {
+        return false;
+    };
+    let mut assigned = false;
+    for todo in todos.iter_mut().filter_map(Value::as_object_mut) {
+        if !todo.contains_key("uuid") {
+            todo.insert("uuid".to_string(), Value::from(Uuid::new_v4().to_string()));
+            assigned = true;
+        }
+    }
+    assigned
+}
@@ -1,15 +1,189 @@
 // 외부 크레이트 임포트
-use chrono::{DateTime, Local};      // 날짜/시간 처리를 위한 chrono 크레이트
+use chrono::{DateTime, Duration, Local}; // 날짜/시간 처리를 위한 chrono 크레이트
+use crate::fields::FieldValue;       // 사용자 정의 필드 값
+use crate::recurrence::Recurrence;   // 반복 규칙
+use clap::ValueEnum;                 // 커맨드라인에서 enum 값을 받기 위한 clap 트레이트
 use serde::{Deserialize, Serialize}; // JSON 직렬화/역직렬화를 위한 serde
+use std::collections::{BTreeMap, BTreeSet}; // 이름순으로 정렬되는 필드 값, 태그 집합
 use std::fmt;                        // Display 트레이트 구현을 위한 표준 라이브러리
+use uuid::Uuid;                      // 파일을 합쳐도 겹치지 않는 고유 식별자
+
+// 시간 기록 구간 - end가 None이면 아직 타이머가 돌고 있는 중
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TimeEntry {
+    pub start: DateTime<Local>,          // 시작 시각
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<DateTime<Local>>,    // 종료 시각
+}
+
+impl TimeEntry {
+    // 구간의 길이 (진행 중이면 now까지)
+    pub fn duration(&self, now: DateTime<Local>) -> Duration {
+        self.end.unwrap_or(now) - self.start
+    }
+}
+
+// 알림 - 지정한 시각에 todo remind --watch 가 한 번 알려줌
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Reminder {
+    pub at: DateTime<Local>,             // 알릴 시각
+    #[serde(default)]
+    pub fired: bool,                     // 이미 알렸는지 여부 (중복 알림 방지)
+}
+
+// 체크리스트 항목 - 하위 할일보다 가벼운, 할일 안의 단계
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CheckItem {
+    pub text: String,                    // 단계 설명
+    #[serde(default)]
+    pub done: bool,                      // 체크 여부
+}
+
+// 첨부 - 웹 링크 또는 첨부 저장소에 복사된 파일
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Attachment {
+    Link { url: String },                // URL은 링크로만 저장
+    File { name: String, file: String }, // 원래 파일 이름, 저장소 안의 파일 이름 (내용 해시)
+}
+
+impl fmt::Display for Attachment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Attachment::Link { url } => write!(f, "🔗 {}", url),
+            Attachment::File { name, .. } => write!(f, "📄 {}", name),
+        }
+    }
+}
+
+// 할일 진행 상태
+// 완료(done)와 취소(cancelled)는 더 이상 할 일이 없는 "닫힌" 상태
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[serde(rename_all = "kebab-case")] // JSON에는 "todo", "in-progress" 처럼 저장
+pub enum Status {
+    #[default]
+    Todo,                            // 할 일 (기본값)
+    InProgress,                      // 진행 중
+    Blocked,                         // 막힘 (외부 요인으로 진행 불가)
+    Done,                            // 완료
+    Cancelled,                       // 취소
+}
+
+impl Status {
+    // 통계/선택지 출력에 사용할 전체 목록
+    pub const ALL: [Status; 5] = [
+        Status::Todo,
+        Status::InProgress,
+        Status::Blocked,
+        Status::Done,
+        Status::Cancelled,
+    ];
+
+    // 완료 또는 취소되어 더 이상 진행할 필요가 없는 상태인지 확인
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Status::Done | Status::Cancelled)
+    }
+
+    // 목록/통계 출력용 한글 이름
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Todo => "할 일",
+            Status::InProgress => "진행 중",
+            Status::Blocked => "막힘",
+            Status::Done => "완료",
+            Status::Cancelled => "취소",
+        }
+    }
+
+    // 목록에서 ID 옆에 표시할 기호
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Status::Todo => "○",
+            Status::InProgress => "▶",
+            Status::Blocked => "■",
+            Status::Done => "✓",
+            Status::Cancelled => "✗",
+        }
+    }
+}
+
+// 우선순위 열거형
+// 변수 선언 순서가 곧 정렬 순서 (High가 가장 앞)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, ValueEnum)]
+#[serde(rename_all = "lowercase")]   // JSON에는 "high", "medium" 처럼 소문자로 저장
+pub enum Priority {
+    High,
+    Medium,
+    Low,
+    #[default]
+    None,                            // 우선순위 미지정 (기본값)
+}
+
+impl Priority {
+    // 목록/통계 출력용 한글 이름
+    pub fn label(&self) -> &'static str {
+        match self {
+            Priority::High => "높음",
+            Priority::Medium => "보통",
+            Priority::Low => "낮음",
+            Priority::None => "없음",
+        }
+    }
+
+    // 제목 앞에 붙는 표시 기호
+    pub fn marker(&self) -> &'static str {
+        match self {
+            Priority::High => "!!! ",
+            Priority::Medium => "!! ",
+            Priority::Low => "! ",
+            Priority::None => "",
+        }
+    }
+}
 
 // Todo 구조체 정의
 // derive 매크로로 자동으로 트레이트 구현
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Todo {
-    pub id: usize,                     // 고유 식별자
+    pub id: usize,                     // 화면에 표시하는 짧은 번호
+    pub uuid: Uuid,                    // 고유 식별자 - 여러 파일을 합쳐도 겹치지 않음 (UUID가 없는 예전 파일은 storage의 마이그레이션에서 부여)
     pub title: String,                 // 할일 제목
-    pub completed: bool,               // 완료 여부
+    #[serde(default)]
+    pub status: Status,                // 진행 상태
+    #[serde(default)]                  // 예전 todos.json에는 없는 필드이므로 기본값 사용
+    pub priority: Priority,            // 우선순위
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<DateTime<Local>>,  // 마감 기한 (없을 수 있음)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait: Option<DateTime<Local>>, // 시작일 - 이 시각 전까지 목록에서 숨김
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub tags: BTreeSet<String>,        // 태그 (이름순 정렬, 중복 없음)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,       // 점으로 구분된 프로젝트 경로 (예: work.backend.api)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<usize>,         // 상위 할일 ID (하위 할일인 경우)
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub depends_on: BTreeSet<usize>,   // 먼저 끝나야 하는 할일 ID들
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<Recurrence>, // 반복 규칙 (완료하면 다음 회차 생성)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,         // 여러 줄 메모
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub time_entries: Vec<TimeEntry>,  // 작업 시간 기록
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, FieldValue>, // 사용자 정의 필드 값 (설정 파일의 스키마로 검증)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reminders: Vec<Reminder>,      // 알림 시각들
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checklist: Vec<CheckItem>,     // 체크리스트 항목들
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,  // 링크와 첨부 파일
+    #[serde(default)]
+    pub position: usize,               // 수동 정렬 순서 (작을수록 앞, ID와 무관)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<DateTime<Local>>, // 보관함으로 옮겨진 시각 (보관함 파일에서만 사용)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Local>>,  // 삭제된 시각 (휴지통 파일에서만 사용)
     pub created_at: DateTime<Local>,   // 생성 시간 (로컬 시간대)
     pub updated_at: DateTime<Local>,   // 마지막 수정 시간
 }
@@ -22,33 +196,323 @@ impl Todo {
         let now = Local::now();  // 현재 로컬 시간 가져오기
         Self {
             id,
+            uuid: Uuid::new_v4(),
             title,
-            completed: false,    // 새 할일은 항상 미완료 상태로 시작
+            status: Status::Todo, // 새 할일은 항상 "할 일" 상태로 시작
+            priority: Priority::None,
+            due: None,
+            wait: None,
+            tags: BTreeSet::new(),
+            project: None,
+            parent: None,
+            depends_on: BTreeSet::new(),
+            recurrence: None,
+            notes: None,
+            time_entries: Vec::new(),
+            fields: BTreeMap::new(),
+            reminders: Vec::new(),
+            checklist: Vec::new(),
+            attachments: Vec::new(),
+            position: 0,          // 목록에 추가될 때 맨 뒤 순서로 정해짐
+            archived_at: None,
+            deleted_at: None,
             created_at: now,
             updated_at: now,     // 생성시 두 시간 필드는 동일
         }
     }
 
     // 완료 상태를 토글하는 메서드
+    // 닫힌 상태(완료/취소)면 다시 "할 일"로, 아니면 "완료"로 변경
     // &mut self: 자기 자신을 가변 참조로 받아 수정
     pub fn toggle(&mut self) {
-        self.completed = !self.completed;  // 불린 값 반전
+        let next = if self.is_closed() { Status::Todo } else { Status::Done };
+        self.set_status(next);
+    }
+
+    // 진행 상태를 변경하는 메서드
+    pub fn set_status(&mut self, status: Status) {
+        self.status = status;
         self.updated_at = Local::now();    // 수정 시간 업데이트
     }
 
+    // 완료 또는 취소된 할일인지 확인
+    pub fn is_closed(&self) -> bool {
+        self.status.is_closed()
+    }
+
     // 제목을 업데이트하는 메서드
     pub fn update_title(&mut self, new_title: String) {
         self.title = new_title;         // 새 제목으로 변경
         self.updated_at = Local::now(); // 수정 시간 업데이트
     }
+
+    // 우선순위를 변경하는 메서드
+    pub fn set_priority(&mut self, priority: Priority) {
+        self.priority = priority;
+        self.updated_at = Local::now();
+    }
+
+    // 마감 기한을 설정하거나 (None이면) 제거하는 메서드
+    pub fn set_due(&mut self, due: Option<DateTime<Local>>) {
+        self.due = due;
+        self.updated_at = Local::now();
+    }
+
+    // 태그 추가 - 새로 추가되었으면 true (빈 태그는 무시)
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return false;
+        }
+        let added = self.tags.insert(tag.to_string());
+        if added {
+            self.updated_at = Local::now();
+        }
+        added
+    }
+
+    // 태그 제거 - 실제로 있었으면 true
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let removed = self.tags.remove(tag);
+        if removed {
+            self.updated_at = Local::now();
+        }
+        removed
+    }
+
+    // 특정 태그가 붙어 있는지 확인
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(tag)
+    }
+
+    // 프로젝트를 설정하거나 (None 또는 빈 문자열이면) 제거하는 메서드
+    pub fn set_project(&mut self, project: Option<&str>) {
+        self.project = project.and_then(normalize_project);
+        self.updated_at = Local::now();
+    }
+
+    // 반복 규칙을 설정하거나 (None이면) 해제하는 메서드
+    pub fn set_recurrence(&mut self, recurrence: Option<Recurrence>) {
+        self.recurrence = recurrence;
+        self.updated_at = Local::now();
+    }
+
+    // 완료된 반복 할일로부터 다음 회차 할일 생성
+    // 반복 규칙은 새 할일로 옮겨가고, 완료된 할일에는 기록만 남음
+    pub fn next_occurrence(&mut self, next_id: usize, completed_at: DateTime<Local>) -> Option<Todo> {
+        if self.status != Status::Done {
+            return None;
+        }
+        let next_due = self.recurrence.as_ref()?.next_due(self.due, completed_at)?;
+
+        let mut next = self.clone();
+        next.id = next_id;
+        next.uuid = Uuid::new_v4();      // 다음 회차는 별개의 할일
+        next.status = Status::Todo;
+        next.due = Some(next_due);
+        next.time_entries.clear();       // 시간 기록은 회차별로 따로 관리
+        for item in &mut next.checklist {
+            item.done = false;           // 체크리스트는 회차마다 처음부터 다시 진행
+        }
+        // 알림은 마감일이 밀린 만큼 함께 밀어서 다시 알림 (기존 마감일이 없으면 제거)
+        match self.due {
+            Some(due) => {
+                let shift = next_due - due;
+                for reminder in &mut next.reminders {
+                    reminder.at += shift;
+                    reminder.fired = false;
+                }
+            }
+            None => next.reminders.clear(),
+        }
+        next.created_at = completed_at;
+        next.updated_at = completed_at;
+
+        self.recurrence = None;
+        self.updated_at = completed_at;
+        Some(next)
+    }
+
+    // 메모를 설정하는 메서드 (공백뿐인 메모는 제거)
+    pub fn set_notes(&mut self, notes: &str) {
+        let notes = notes.trim_end();
+        self.notes = if notes.trim().is_empty() {
+            None
+        } else {
+            Some(notes.to_string())
+        };
+        self.updated_at = Local::now();
+    }
+
+    // 사용자 정의 필드 값을 설정하거나 (None이면) 제거하는 메서드
+    pub fn set_field(&mut self, name: &str, value: Option<FieldValue>) {
+        match value {
+            Some(value) => self.fields.insert(name.to_string(), value),
+            None => self.fields.remove(name),
+        };
+        self.updated_at = Local::now();
+    }
+
+    // 알림 추가 - 같은 시각의 알림이 이미 있으면 false
+    pub fn add_reminder(&mut self, at: DateTime<Local>) -> bool {
+        if self.reminders.iter().any(|r| r.at == at) {
+            return false;
+        }
+        self.reminders.push(Reminder { at, fired: false });
+        self.reminders.sort_by_key(|r| r.at);
+        self.updated_at = Local::now();
+        true
+    }
+
+    // 아직 울리지 않은 알림이 있는지 확인
+    pub fn has_pending_reminder(&self) -> bool {
+        !self.is_closed() && self.reminders.iter().any(|r| !r.fired)
+    }
+
+    // 체크리스트 항목 추가 - 추가된 항목의 번호(1부터) 반환
+    pub fn add_check_item(&mut self, text: &str) -> usize {
+        self.checklist.push(CheckItem { text: text.trim().to_string(), done: false });
+        self.updated_at = Local::now();
+        self.checklist.len()
+    }
+
+    // n번째(1부터) 체크리스트 항목의 체크 상태를 뒤집음 - 바뀐 상태 반환, 없는 번호면 None
+    pub fn toggle_check_item(&mut self, n: usize) -> Option<bool> {
+        let item = self.checklist.get_mut(n.checked_sub(1)?)?;
+        item.done = !item.done;
+        self.updated_at = Local::now();
+        Some(item.done)
+    }
+
+    // n번째(1부터) 체크리스트 항목 제거 - 제거된 항목 반환
+    pub fn remove_check_item(&mut self, n: usize) -> Option<CheckItem> {
+        let index = n.checked_sub(1).filter(|i| *i < self.checklist.len())?;
+        self.updated_at = Local::now();
+        Some(self.checklist.remove(index))
+    }
+
+    // 체크리스트 진행 상황 (체크된 수, 전체 수)
+    pub fn checklist_progress(&self) -> (usize, usize) {
+        let done = self.checklist.iter().filter(|item| item.done).count();
+        (done, self.checklist.len())
+    }
+
+    // 첨부 추가 - 같은 링크/파일이 이미 있으면 false
+    pub fn add_attachment(&mut self, attachment: Attachment) -> bool {
+        if self.attachments.contains(&attachment) {
+            return false;
+        }
+        self.attachments.push(attachment);
+        self.updated_at = Local::now();
+        true
+    }
+
+    // n번째(1부터) 첨부 제거 - 제거된 첨부 반환
+    pub fn remove_attachment(&mut self, n: usize) -> Option<Attachment> {
+        let index = n.checked_sub(1).filter(|i| *i < self.attachments.len())?;
+        self.updated_at = Local::now();
+        Some(self.attachments.remove(index))
+    }
+
+    // 타이머가 돌고 있는지 확인
+    pub fn is_tracking(&self) -> bool {
+        self.time_entries.iter().any(|e| e.end.is_none())
+    }
+
+    // 타이머 시작 - 이미 돌고 있으면 false
+    pub fn start_timer(&mut self, now: DateTime<Local>) -> bool {
+        if self.is_tracking() {
+            return false;
+        }
+        self.time_entries.push(TimeEntry { start: now, end: None });
+        self.updated_at = now;
+        true
+    }
+
+    // 타이머 정지 - 이번 구간의 길이를 반환 (돌고 있지 않았으면 None)
+    pub fn stop_timer(&mut self, now: DateTime<Local>) -> Option<Duration> {
+        let entry = self.time_entries.iter_mut().find(|e| e.end.is_none())?;
+        entry.end = Some(now);
+        self.updated_at = now;
+        Some(entry.duration(now))
+    }
+
+    // 지금까지 기록된 전체 시간 (진행 중인 구간 포함)
+    pub fn tracked_time(&self, now: DateTime<Local>) -> Duration {
+        self.time_entries.iter().map(|e| e.duration(now)).sum()
+    }
+
+    // 상위 할일을 설정하거나 (None이면) 최상위 할일로 만드는 메서드
+    pub fn set_parent(&mut self, parent: Option<usize>) {
+        self.parent = parent;
+        self.updated_at = Local::now();
+    }
+
+    // 주어진 프로젝트 또는 그 하위 프로젝트에 속하는지 확인
+    // "work"는 "work", "work.backend"와 일치하지만 "workshop"과는 일치하지 않음
+    pub fn in_project(&self, project: &str) -> bool {
+        match &self.project {
+            Some(own) => {
+                own == project
+                    || own
+                        .strip_prefix(project)
+                        .is_some_and(|rest| rest.starts_with('.'))
+            }
+            None => false,
+        }
+    }
+
+    // 시작일을 설정하거나 (None이면) 제거하는 메서드
+    pub fn set_wait(&mut self, wait: Option<DateTime<Local>>) {
+        self.wait = wait;
+        self.updated_at = Local::now();
+    }
+
+    // 시작일이 아직 오지 않아 숨겨야 하는지 확인
+    pub fn is_waiting(&self, now: DateTime<Local>) -> bool {
+        self.wait.is_some_and(|wait| wait > now)
+    }
+
+    // 기한이 지났는지 확인 (완료/취소된 할일은 제외)
+    pub fn is_overdue(&self, now: DateTime<Local>) -> bool {
+        !self.is_closed() && self.due.is_some_and(|due| due < now)
+    }
+
+    // 오늘 안에 마감인지 확인 (이미 지난 것은 제외)
+    pub fn is_due_today(&self, now: DateTime<Local>) -> bool {
+        !self.is_closed()
+            && self
+                .due
+                .is_some_and(|due| due >= now && due.date_naive() == now.date_naive())
+    }
+
+    // 앞으로 주어진 기간 안에 마감인지 확인 (오늘 마감 포함)
+    pub fn is_due_within(&self, now: DateTime<Local>, within: Duration) -> bool {
+        !self.is_closed() && self.due.is_some_and(|due| due >= now && due <= now + within)
+    }
+}
+
+// 프로젝트 경로 정리 - 공백과 빈 구간을 제거 ("work..api." → "work.api")
+// 남는 구간이 없으면 None
+pub fn normalize_project(project: &str) -> Option<String> {
+    let segments: Vec<&str> = project
+        .split('.')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+    if segments.is_empty() {
+        None
+    } else {
+        Some(segments.join("."))
+    }
 }
 
 // Display 트레이트 구현 - Todo를 문자열로 표시하는 방법 정의
 // println!("{}", todo) 형태로 사용 가능
 impl fmt::Display for Todo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // 완료 상태에 따라 다른 기호 사용
-        let status = if self.completed { "✓" } else { "○" };
+        // 진행 상태에 따라 다른 기호 사용
+        let status = self.status.symbol();
         
         // 날짜 포맷 지정 (년-월-일 시:분)
         let created = self.created_at.format("%Y-%m-%d %H:%M");
@@ -56,8 +520,56 @@ impl fmt::Display for Todo {
         // 포맷터에 출력 형식 작성
         write!(
             f,
-            "[{}] {} {} (생성: {})",
-            self.id, status, self.title, created
-        )
+            "[{}] {} {}{}",
+            self.id, status, self.priority.marker(), self.title
+        )?;
+
+        // 태그는 제목 뒤에 #태그 형태로 표시
+        for tag in &self.tags {
+            write!(f, " #{}", tag)?;
+        }
+
+        // 반복 할일 표시
+        if let Some(recurrence) = &self.recurrence {
+            write!(f, " 🔁{}", recurrence)?;
+        }
+
+        // 타이머가 돌고 있으면 표시
+        if self.is_tracking() {
+            write!(f, " ⏱️")?;
+        }
+
+        // 울릴 알림이 남아 있으면 표시
+        if self.has_pending_reminder() {
+            write!(f, " 🔔")?;
+        }
+
+        // 첨부가 있으면 표시 (todo open으로 확인)
+        if !self.attachments.is_empty() {
+            write!(f, " 📎")?;
+        }
+
+        // 메모가 있으면 표시 (내용은 todo show로 확인)
+        if self.notes.is_some() {
+            write!(f, " 📝")?;
+        }
+
+        // 프로젝트는 @프로젝트 형태로 표시
+        if let Some(project) = &self.project {
+            write!(f, " @{}", project)?;
+        }
+
+        write!(f, " (생성: {}", created)?;
+
+        // 마감 기한이 있으면 함께 표시
+        if let Some(due) = self.due {
+            write!(f, ", 마감: {}", due.format("%Y-%m-%d %H:%M"))?;
+        }
+
+        // 아직 시작일이 오지 않았으면 (--all 로 볼 때) 시작일 표시
+        if let Some(wait) = self.wait.filter(|_| self.is_waiting(Local::now())) {
+            write!(f, ", 💤 {} 부터", wait.format("%Y-%m-%d %H:%M"))?;
+        }
+        write!(f, ")")
     }
 }
\ No newline at end of file